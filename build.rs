@@ -1,3 +1,250 @@
+use std::{collections::BTreeMap, env, fmt::Write, fs, path::Path};
+
+#[allow(dead_code)]
+#[path = "src/message_syntax.rs"]
+mod message_syntax;
+
+const LOCALES_DIR: &str = "locales";
+/// the fallback locale, which every key is expected to exist in
+const KEYS_LOCALE: &str = "locales/en-US.yml";
+
+#[derive(Default)]
+struct KeyModule {
+    keys: BTreeMap<String, Key>,
+    modules: BTreeMap<String, KeyModule>,
+}
+
+struct Key {
+    key: String,
+    message: String,
+    arguments: Vec<String>,
+}
+
 fn main() {
-    println!("cargo::rerun-if-changed=locales");
+    println!("cargo::rerun-if-changed={LOCALES_DIR}");
+    println!("cargo::rerun-if-changed=src/message_syntax.rs");
+
+    let yaml = fs::read_to_string(KEYS_LOCALE)
+        .unwrap_or_else(|error| panic!("unable to read {KEYS_LOCALE}: {error}"));
+    let value: serde_yaml::Value = serde_yaml::from_str(&yaml)
+        .unwrap_or_else(|error| panic!("unable to parse {KEYS_LOCALE}: {error}"));
+
+    let mut root = KeyModule::default();
+
+    insert_keys(&mut root, &value, &[]);
+
+    let mut generated = String::new();
+
+    write_module(&mut generated, &root, 0);
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+
+    fs::write(Path::new(&out_dir).join("keys.rs"), generated).expect("unable to write keys.rs");
+    fs::write(
+        Path::new(&out_dir).join("bundled_locales.rs"),
+        bundled_locales(),
+    )
+    .expect("unable to write bundled_locales.rs");
+
+    check_other_locales();
+}
+
+/// `(locale, contents)` of every locale file, so that the crate can look a key up in the bundled
+/// locales without going through rust-i18n's internals
+fn bundled_locales() -> String {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+    let mut paths: Vec<_> = fs::read_dir(LOCALES_DIR)
+        .unwrap_or_else(|error| panic!("unable to read {LOCALES_DIR}: {error}"))
+        .map(|entry| {
+            entry
+                .unwrap_or_else(|error| panic!("unable to read {LOCALES_DIR}: {error}"))
+                .path()
+        })
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "yml" || extension == "yaml")
+        })
+        .collect();
+
+    paths.sort();
+
+    let mut generated = String::from("&[\n");
+
+    for path in paths {
+        let locale = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_else(|| panic!("{} isn't named after a locale", path.display()));
+        let path = Path::new(&manifest_dir).join(&path).display().to_string();
+
+        writeln!(generated, "    ({locale:?}, include_str!({path:?})),").unwrap();
+    }
+
+    generated.push(']');
+    generated
+}
+
+/// a message that doesn't parse would be shown as it is, so none of them are allowed to ship;
+/// the keys locale is already checked while its keys are generated
+fn check_other_locales() {
+    let entries = fs::read_dir(LOCALES_DIR)
+        .unwrap_or_else(|error| panic!("unable to read {LOCALES_DIR}: {error}"));
+
+    for entry in entries {
+        let path = entry
+            .unwrap_or_else(|error| panic!("unable to read {LOCALES_DIR}: {error}"))
+            .path();
+
+        if path == Path::new(KEYS_LOCALE)
+            || !path
+                .extension()
+                .is_some_and(|extension| extension == "yml" || extension == "yaml")
+        {
+            continue;
+        }
+
+        let path = path.display().to_string();
+        let yaml = fs::read_to_string(&path)
+            .unwrap_or_else(|error| panic!("unable to read {path}: {error}"));
+        let value: serde_yaml::Value = serde_yaml::from_str(&yaml)
+            .unwrap_or_else(|error| panic!("unable to parse {path}: {error}"));
+
+        check_messages(&value, "", &path);
+    }
+}
+
+fn check_messages(value: &serde_yaml::Value, prefix: &str, path: &str) {
+    let Some(mapping) = value.as_mapping() else {
+        return;
+    };
+
+    for (segment, value) in mapping {
+        let Some(segment) = segment.as_str() else {
+            continue;
+        };
+
+        let key = if prefix.is_empty() {
+            segment.to_string()
+        } else {
+            format!("{prefix}.{segment}")
+        };
+
+        match value {
+            serde_yaml::Value::Mapping(_) => check_messages(value, &key, path),
+            serde_yaml::Value::String(message) => {
+                if let Err(error) = message_syntax::parse_message(message) {
+                    panic!("\"{key}\" in {path}: {error}");
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+fn insert_keys(module: &mut KeyModule, value: &serde_yaml::Value, path: &[&str]) {
+    let Some(mapping) = value.as_mapping() else {
+        return;
+    };
+
+    for (segment, value) in mapping {
+        let Some(segment) = segment.as_str() else {
+            continue;
+        };
+
+        let path = [path, &[segment]].concat();
+        let key = path.join(".");
+
+        // keys that can't be spelled in rust are still usable as plain strings
+        if !is_identifier(segment) {
+            println!(
+                "cargo::warning=no typed key for \"{key}\"; \"{segment}\" is not an identifier"
+            );
+            continue;
+        }
+
+        match value {
+            serde_yaml::Value::Mapping(_) => insert_keys(
+                module.modules.entry(segment.to_string()).or_default(),
+                value,
+                &path,
+            ),
+            serde_yaml::Value::String(message) => {
+                let parts = message_syntax::parse_message(message)
+                    .unwrap_or_else(|error| panic!("\"{key}\" in {KEYS_LOCALE}: {error}"));
+                let arguments = message_syntax::message_arguments(&parts);
+
+                if let Some(argument) = arguments.iter().find(|name| !is_identifier(name)) {
+                    println!(
+                        "cargo::warning=no typed key for \"{key}\"; its argument \"{argument}\" is not an identifier"
+                    );
+                    continue;
+                }
+
+                module.keys.insert(
+                    segment.to_uppercase(),
+                    Key {
+                        key,
+                        message: message.clone(),
+                        arguments,
+                    },
+                );
+            }
+            _ => (),
+        }
+    }
+}
+
+fn write_module(generated: &mut String, module: &KeyModule, depth: usize) {
+    let indent = "    ".repeat(depth);
+
+    for (name, key) in &module.keys {
+        for line in key.message.lines() {
+            writeln!(generated, "{indent}/// {line}").unwrap();
+        }
+
+        let fields: String = key
+            .arguments
+            .iter()
+            .map(|argument| format!(" pub {argument}: (),"))
+            .collect();
+
+        // a key without arguments is a unit struct so that it can be passed around as a value
+        if fields.is_empty() {
+            writeln!(generated, "{indent}pub struct {name};").unwrap();
+        } else {
+            writeln!(generated, "{indent}pub struct {name} {{{fields} }}").unwrap();
+        }
+        writeln!(
+            generated,
+            "{indent}impl {}LocaleKey for {name} {{ const KEY: &'static str = {:?}; }}",
+            "super::".repeat(depth),
+            key.key
+        )
+        .unwrap();
+    }
+
+    for (name, module) in &module.modules {
+        writeln!(generated, "{indent}pub mod {name} {{").unwrap();
+        write_module(generated, module, depth + 1);
+        writeln!(generated, "{indent}}}").unwrap();
+    }
+}
+
+/// ascii identifiers that aren't keywords, so that they can be used as module and field names
+fn is_identifier(name: &str) -> bool {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+        "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+        "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+        "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "try",
+        "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+
+    let mut chars = name.chars();
+
+    chars
+        .next()
+        .is_some_and(|char| char.is_ascii_alphabetic() || char == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+        && !KEYWORDS.contains(&name)
 }
@@ -0,0 +1,185 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    Data, DeriveInput, Fields, GenericArgument, LitStr, PathArguments, Type, parse_macro_input,
+    spanned::Spanned,
+};
+
+/// derives `hoshi::options::CommandOptions` for a struct with named fields, each field becoming
+/// an option of the same name; `Option<T>` fields are optional and everything else is required
+///
+/// every field needs `#[option(description = "...")]`, may rename the option with
+/// `#[option(name = "...")]` and may enable autocomplete for it with `#[option(autocomplete)]`
+#[proc_macro_derive(CommandOptions, attributes(option))]
+pub fn derive_command_options(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_command_options(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct OptionField {
+    ident: syn::Ident,
+    name: LitStr,
+    description: LitStr,
+    value_type: Type,
+    required: bool,
+    autocomplete: bool,
+}
+
+fn expand_command_options(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "CommandOptions can only be derived for structs",
+        ));
+    };
+
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new(
+            data.fields.span(),
+            "CommandOptions can only be derived for structs with named fields",
+        ));
+    };
+
+    let option_fields = fields
+        .named
+        .iter()
+        .map(parse_option_field)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let builders = option_fields.iter().map(|field| {
+        let OptionField {
+            name,
+            description,
+            value_type,
+            required,
+            autocomplete,
+            ..
+        } = field;
+
+        let autocomplete = autocomplete.then(|| quote! { option.autocomplete = Some(true); });
+
+        quote! {
+            {
+                let mut option = <#value_type as ::hoshi::options::OptionValue>::option(#name, #description);
+                option.required = Some(#required);
+                #autocomplete
+                option
+            }
+        }
+    });
+
+    let extractors = option_fields.iter().map(|field| {
+        let OptionField {
+            ident,
+            name,
+            value_type,
+            required,
+            ..
+        } = field;
+
+        let extract = quote! {
+            ::hoshi::options::extract::<#value_type>(resolver, #name)?
+        };
+
+        if *required {
+            quote! {
+                #ident: #extract.ok_or_else(|| ::hoshi::options::missing_option_error(#name))?
+            }
+        } else {
+            quote! { #ident: #extract }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::hoshi::options::CommandOptions for #ident #type_generics #where_clause {
+            fn options() -> ::std::vec::Vec<::hoshi::options::CommandOption> {
+                ::std::vec![#(#builders),*]
+            }
+
+            fn from_resolver(
+                resolver: &::hoshi::resolver::ApplicationCommandInteractionChatInputDataResolver,
+            ) -> ::hoshi::error::BotResult<Self> {
+                Ok(Self {
+                    #(#extractors),*
+                })
+            }
+        }
+    })
+}
+
+fn parse_option_field(field: &syn::Field) -> syn::Result<OptionField> {
+    let ident = field.ident.clone().expect("fields are named");
+
+    let mut name = None;
+    let mut description = None;
+    let mut autocomplete = false;
+
+    for attribute in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("option"))
+    {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("description") {
+                description = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("autocomplete") {
+                autocomplete = true;
+            } else {
+                return Err(meta.error("unsupported option attribute"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    let description = description.ok_or_else(|| {
+        syn::Error::new(
+            field.span(),
+            "missing `#[option(description = \"...\")]` attribute",
+        )
+    })?;
+    let name = name.unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+
+    let (value_type, required) = match option_inner_type(&field.ty) {
+        Some(inner_type) => (inner_type.clone(), false),
+        None => (field.ty.clone(), true),
+    };
+
+    Ok(OptionField {
+        ident,
+        name,
+        description,
+        value_type,
+        required,
+        autocomplete,
+    })
+}
+
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+
+    let segment = type_path.path.segments.last()?;
+
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+
+    match arguments.args.first()? {
+        GenericArgument::Type(inner_type) => Some(inner_type),
+        _ => None,
+    }
+}
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use twilight_model::id::{Id, marker::GuildMarker};
+
+use crate::config::ConfigArgs;
+
+/// runs the bot, or manages its command schemas
+#[derive(Parser, Debug)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+    #[command(flatten)]
+    pub config: ConfigArgs,
+    /// overwrite the registered commands even when they match the schemas
+    #[arg(long, global = true)]
+    pub force: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CliCommand {
+    /// run the bot; what happens without a subcommand
+    Run,
+    /// inspect the command schemas without talking to discord
+    #[command(subcommand)]
+    Schema(SchemaCommand),
+    /// sync the commands globally and to the dev guilds, or only to the given guild
+    Register {
+        /// registers every command, dev guild ones included, to just this guild
+        #[arg(long)]
+        guild: Option<Id<GuildMarker>>,
+    },
+    /// remove the commands globally and from the dev guilds, or only from the given guild
+    Unregister {
+        #[arg(long)]
+        guild: Option<Id<GuildMarker>>,
+    },
+    /// inspect the locale files
+    #[command(subcommand)]
+    Locales(LocalesCommand),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SchemaCommand {
+    /// print the localized commands as json, as they are sent to discord
+    Dump {
+        /// the commands of the dev guilds instead of the global ones
+        #[arg(long)]
+        dev_guild: bool,
+    },
+    /// compare the commands with a snapshot made by `schema dump`; exits with 1 if they differ
+    Diff {
+        snapshot: PathBuf,
+        /// the commands of the dev guilds instead of the global ones
+        #[arg(long)]
+        dev_guild: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LocalesCommand {
+    /// compare every locale file with en-US: missing and extra keys, mismatched placeholders and
+    /// coverage; exits with 1 if there are any problems
+    Check {
+        /// the directory with the locale files
+        #[arg(long, default_value = "locales")]
+        dir: PathBuf,
+        #[arg(long, value_enum, default_value_t)]
+        format: ReportFormat,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default)]
+pub enum ReportFormat {
+    #[default]
+    Text,
+    Json,
+}
@@ -0,0 +1,306 @@
+use std::{
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use tokio::sync::oneshot;
+use twilight_gateway::Event;
+use twilight_model::{
+    application::interaction::{Interaction as EventInteraction, InteractionData, InteractionType},
+    gateway::payload::incoming::InteractionCreate,
+    id::{
+        Id,
+        marker::{MessageMarker, UserMarker},
+    },
+};
+
+use crate::{
+    context::BotContext, interaction::ApplicationInteraction,
+    resolver::MessageComponentInteractionDataResolver,
+};
+
+/// which component interactions a collector wants; unset fields match anything
+#[derive(Debug, Default, Clone)]
+pub struct ComponentFilter {
+    message_id: Option<Id<MessageMarker>>,
+    user_id: Option<Id<UserMarker>>,
+    custom_id_prefix: Option<String>,
+}
+
+impl ComponentFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn message(mut self, message_id: Id<MessageMarker>) -> Self {
+        self.message_id = Some(message_id);
+        self
+    }
+
+    pub fn user(mut self, user_id: Id<UserMarker>) -> Self {
+        self.user_id = Some(user_id);
+        self
+    }
+
+    pub fn custom_id_prefix(mut self, custom_id_prefix: impl Into<String>) -> Self {
+        self.custom_id_prefix = Some(custom_id_prefix.into());
+        self
+    }
+
+    fn matches(&self, event_interaction: &EventInteraction) -> bool {
+        let Some(InteractionData::MessageComponent(component_data)) = &event_interaction.data
+        else {
+            return false;
+        };
+
+        let message_id = event_interaction.message.as_ref().map(|message| message.id);
+
+        self.message_id.is_none_or(|id| message_id == Some(id))
+            && self
+                .user_id
+                .is_none_or(|id| event_interaction.author_id() == Some(id))
+            && self
+                .custom_id_prefix
+                .as_ref()
+                .is_none_or(|prefix| component_data.custom_id.starts_with(prefix))
+    }
+}
+
+#[derive(Debug)]
+struct PendingCollector {
+    filter: ComponentFilter,
+    sender: oneshot::Sender<EventInteraction>,
+}
+
+/// lets a handler wait for the next component interaction matching a filter instead of routing
+/// it through the component registry; fed by the gateway loop before events are dispatched
+#[derive(Debug, Default)]
+pub struct ComponentCollectors {
+    pending: Mutex<Vec<PendingCollector>>,
+    closed: AtomicBool,
+}
+
+impl ComponentCollectors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// hands component interactions over to the oldest collector waiting for them; returns the
+    /// event back when nothing claimed it so it can be dispatched as usual
+    pub fn intercept(&self, event: Event) -> Option<Event> {
+        let Event::InteractionCreate(interaction_create) = event else {
+            return Some(event);
+        };
+
+        if interaction_create.kind != InteractionType::MessageComponent {
+            return Some(Event::InteractionCreate(interaction_create));
+        }
+
+        let mut event_interaction = interaction_create.0;
+        let mut pending = self.pending.lock().expect("collector lock poisoned");
+
+        pending.retain(|collector| !collector.sender.is_closed());
+
+        while let Some(index) = pending
+            .iter()
+            .position(|collector| collector.filter.matches(&event_interaction))
+        {
+            // the receiver can time out between the retain and the send, in which case the
+            // interaction is returned to us and offered to the next collector
+            match pending.remove(index).sender.send(event_interaction) {
+                Ok(()) => return None,
+                Err(returned_interaction) => event_interaction = returned_interaction,
+            }
+        }
+
+        Some(Event::InteractionCreate(Box::new(InteractionCreate(
+            event_interaction,
+        ))))
+    }
+
+    /// ends every pending collector and makes new ones end right away, so that handlers waiting
+    /// on components wrap up instead of holding up shutdown
+    pub fn close(&self) {
+        let mut pending = self.pending.lock().expect("collector lock poisoned");
+
+        self.closed.store(true, Ordering::Release);
+        pending.clear();
+    }
+
+    /// `None` if nothing matched the filter before the timeout or if the collectors were closed
+    pub async fn next(
+        &self,
+        filter: ComponentFilter,
+        timeout: Duration,
+    ) -> Option<EventInteraction> {
+        let (sender, receiver) = oneshot::channel();
+
+        {
+            let mut pending = self.pending.lock().expect("collector lock poisoned");
+
+            // checked under the lock so that a collector can't slip in right after `close`
+            if self.closed.load(Ordering::Acquire) {
+                return None;
+            }
+
+            pending.push(PendingCollector { filter, sender });
+        }
+
+        let collected = tokio::time::timeout(timeout, receiver).await;
+
+        if collected.is_err() {
+            self.pending
+                .lock()
+                .expect("collector lock poisoned")
+                .retain(|collector| !collector.sender.is_closed());
+        }
+
+        collected.ok()?.ok()
+    }
+
+    /// `next` with the collected interaction ready to be responded to
+    pub async fn next_component<'client>(
+        &self,
+        context: &'client BotContext,
+        filter: ComponentFilter,
+        timeout: Duration,
+    ) -> Option<(
+        ApplicationInteraction<'client>,
+        MessageComponentInteractionDataResolver,
+    )> {
+        let mut event_interaction = self.next(filter, timeout).await?;
+
+        let Some(InteractionData::MessageComponent(component_data)) = event_interaction.data.take()
+        else {
+            unreachable!("filters only match message component interactions");
+        };
+
+        Some((
+            context.application_interaction(event_interaction),
+            MessageComponentInteractionDataResolver::from_component_data(*component_data),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn component_event(user_id: u64, custom_id: &str) -> Event {
+        let interaction = serde_json::from_value(json!({
+            "id": "1",
+            "application_id": "2",
+            "type": 3,
+            "token": "token",
+            "version": 1,
+            "authorizing_integration_owners": {},
+            "entitlements": [],
+            "user": {
+                "id": user_id.to_string(),
+                "username": "user",
+                "discriminator": "0",
+                "avatar": null,
+            },
+            "data": {
+                "custom_id": custom_id,
+                "component_type": 2,
+            },
+        }))
+        .unwrap();
+
+        Event::InteractionCreate(Box::new(InteractionCreate(interaction)))
+    }
+
+    fn custom_id_of(event_interaction: &EventInteraction) -> &str {
+        match &event_interaction.data {
+            Some(InteractionData::MessageComponent(component_data)) => &component_data.custom_id,
+            _ => panic!("not a component interaction"),
+        }
+    }
+
+    #[tokio::test]
+    async fn intercept_only_claims_matching_components() {
+        let collectors = ComponentCollectors::new();
+        let filter = ComponentFilter::new()
+            .user(Id::new(10))
+            .custom_id_prefix("test:");
+
+        let (collected, ()) = tokio::join!(collectors.next(filter, TIMEOUT), async {
+            // lets `next` register itself first
+            tokio::task::yield_now().await;
+
+            assert!(collectors.intercept(Event::GatewayHeartbeatAck).is_some());
+            assert!(
+                collectors
+                    .intercept(component_event(11, "test:a"))
+                    .is_some()
+            );
+            assert!(
+                collectors
+                    .intercept(component_event(10, "other:a"))
+                    .is_some()
+            );
+            assert!(
+                collectors
+                    .intercept(component_event(10, "test:b"))
+                    .is_none()
+            );
+            // the collector is used up by then
+            assert!(
+                collectors
+                    .intercept(component_event(10, "test:c"))
+                    .is_some()
+            );
+        });
+
+        assert_eq!(custom_id_of(&collected.unwrap()), "test:b");
+    }
+
+    #[tokio::test]
+    async fn times_out_without_a_match() {
+        let collectors = ComponentCollectors::new();
+
+        let collected = collectors
+            .next(ComponentFilter::new(), Duration::from_millis(10))
+            .await;
+
+        assert!(collected.is_none());
+        assert!(collectors.pending.lock().unwrap().is_empty());
+        assert!(
+            collectors
+                .intercept(component_event(10, "test:a"))
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn close_ends_pending_and_future_collectors() {
+        let collectors = ComponentCollectors::new();
+
+        let (collected, ()) =
+            tokio::join!(collectors.next(ComponentFilter::new(), TIMEOUT), async {
+                tokio::task::yield_now().await;
+                collectors.close();
+            });
+
+        assert!(collected.is_none());
+        assert!(
+            collectors
+                .next(ComponentFilter::new(), TIMEOUT)
+                .await
+                .is_none()
+        );
+        assert!(
+            collectors
+                .intercept(component_event(10, "test:a"))
+                .is_some()
+        );
+    }
+}
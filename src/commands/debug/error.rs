@@ -1,20 +1,57 @@
+use std::sync::Arc;
+
 use twilight_model::application::command::{Command, CommandType};
 use twilight_util::builder::command::CommandBuilder;
 
 use crate::{
     commands::{ALL_CONTEXTS, ALL_INTEGRATIONS},
-    error::BotResult,
+    context::BotContext,
+    error::{BotError, BotResult},
+    interaction::ApplicationCommandInteraction,
+    keys,
+    registry::{BotCommand, CommandFuture},
+    resolver::ApplicationCommandInteractionChatInputDataResolver,
     response::BotResponse,
 };
 
-pub fn schema() -> Command {
-    CommandBuilder::new("error", "error", CommandType::ChatInput)
-        .integration_types(ALL_INTEGRATIONS)
-        .contexts(ALL_CONTEXTS)
-        .build()
+pub struct Error;
+
+impl BotCommand for Error {
+    type Resolver = ApplicationCommandInteractionChatInputDataResolver;
+
+    fn schema(&self) -> Command {
+        CommandBuilder::new("error", "error", CommandType::ChatInput)
+            .integration_types(ALL_INTEGRATIONS)
+            .contexts(ALL_CONTEXTS)
+            .build()
+    }
+
+    fn command<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<'_, '_, Self::Resolver>,
+        context: Arc<BotContext>,
+    ) -> CommandFuture<'a> {
+        Box::pin(command(interaction, context))
+    }
 }
 
-pub async fn command() -> BotResult<Option<BotResponse>> {
-    Err("Hello World!")?;
-    Ok(None)
+pub async fn command(
+    interaction: &mut ApplicationCommandInteraction<
+        '_,
+        '_,
+        ApplicationCommandInteractionChatInputDataResolver,
+    >,
+    context: Arc<BotContext>,
+) -> BotResult<Option<BotResponse>> {
+    let is_owner = interaction
+        .application_interaction
+        .event_interaction
+        .author_id()
+        .is_some_and(|user_id| context.config.is_owner(user_id));
+
+    if !is_owner {
+        return Err(precondition_error!(keys::errors::OWNER_ONLY));
+    }
+
+    Err(BotError::internal("Hello World!"))
 }
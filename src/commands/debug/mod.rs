@@ -1,13 +1,8 @@
-use twilight_model::application::command::Command;
+use crate::registry::CommandRegistry;
 
 pub mod error;
 
-#[cfg(debug_assertions)]
-pub fn command_schemas() -> Vec<Command> {
-    vec![error::schema()]
-}
-
-#[cfg(not(debug_assertions))]
-pub fn command_schemas() -> Vec<Command> {
-    vec![]
+/// only registered when the `debug_commands` feature is enabled, and then only to the dev guilds
+pub fn register(registry: CommandRegistry) -> CommandRegistry {
+    registry.register_in_dev_guilds(error::Error)
 }
@@ -1,19 +1,37 @@
+use std::sync::Arc;
+
 use twilight_model::application::command::{Command, CommandType};
 use twilight_util::builder::command::CommandBuilder;
 
 use crate::{
     commands::{ALL_CONTEXTS, ALL_INTEGRATIONS},
+    context::BotContext,
     error::BotResult,
     interaction::ApplicationCommandInteraction,
+    registry::{BotCommand, CommandFuture},
     resolver::ApplicationCommandInteractionMessageDataResolver,
     response::BotResponse,
 };
 
-pub fn schema() -> Command {
-    CommandBuilder::new("echo", "", CommandType::Message)
-        .integration_types(ALL_INTEGRATIONS)
-        .contexts(ALL_CONTEXTS)
-        .build()
+pub struct Echo;
+
+impl BotCommand for Echo {
+    type Resolver = ApplicationCommandInteractionMessageDataResolver;
+
+    fn schema(&self) -> Command {
+        CommandBuilder::new("echo", "", CommandType::Message)
+            .integration_types(ALL_INTEGRATIONS)
+            .contexts(ALL_CONTEXTS)
+            .build()
+    }
+
+    fn command<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<'_, '_, Self::Resolver>,
+        _: Arc<BotContext>,
+    ) -> CommandFuture<'a> {
+        Box::pin(command(interaction))
+    }
 }
 
 pub async fn command(
@@ -1,19 +1,41 @@
-use twilight_model::application::command::{Command, CommandType};
-use twilight_util::builder::command::CommandBuilder;
+use std::sync::Arc;
+
+use twilight_model::{
+    application::command::{Command, CommandType},
+    channel::message::AllowedMentions,
+};
+use twilight_util::builder::{InteractionResponseDataBuilder, command::CommandBuilder};
 
 use crate::{
     commands::{ALL_CONTEXTS, ALL_INTEGRATIONS},
+    context::BotContext,
     error::BotResult,
     interaction::ApplicationCommandInteraction,
+    keys,
+    registry::{BotCommand, CommandFuture},
     resolver::ApplicationCommandInteractionUserDataResolver,
     response::BotResponse,
 };
 
-pub fn schema() -> Command {
-    CommandBuilder::new("greet", "", CommandType::User)
-        .integration_types(ALL_INTEGRATIONS)
-        .contexts(ALL_CONTEXTS)
-        .build()
+pub struct Greet;
+
+impl BotCommand for Greet {
+    type Resolver = ApplicationCommandInteractionUserDataResolver;
+
+    fn schema(&self) -> Command {
+        CommandBuilder::new("greet", "", CommandType::User)
+            .integration_types(ALL_INTEGRATIONS)
+            .contexts(ALL_CONTEXTS)
+            .build()
+    }
+
+    fn command<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<'_, '_, Self::Resolver>,
+        _: Arc<BotContext>,
+    ) -> CommandFuture<'a> {
+        Box::pin(command(interaction))
+    }
 }
 
 pub async fn command(
@@ -23,14 +45,22 @@ pub async fn command(
         ApplicationCommandInteractionUserDataResolver,
     >,
 ) -> BotResult<Option<BotResponse>> {
+    let user_id = interaction.data_resolver.user.id;
+
     interaction
-        .reply(BotResponse::Content(
-            tl!(
-                interaction,
-                "commands.greet.response",
-                user_id = interaction.data_resolver.user.id.to_string()
-            )
-            .into(),
+        .reply(BotResponse::ResponseData(
+            InteractionResponseDataBuilder::new()
+                .content(tf!(
+                    keys::commands::greet::RESPONSE,
+                    locale = interaction.public_locale().as_str(),
+                    user_id = user_id.to_string()
+                ))
+                // pings the greeted user, but nobody else
+                .allowed_mentions(AllowedMentions {
+                    users: vec![user_id],
+                    ..Default::default()
+                })
+                .build(),
         ))
         .await?;
 
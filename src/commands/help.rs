@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use twilight_model::application::command::{Command, CommandType};
+use twilight_util::builder::command::CommandBuilder;
+
+use crate::{
+    commands::{ALL_CONTEXTS, ALL_INTEGRATIONS},
+    components,
+    context::BotContext,
+    error::BotResult,
+    interaction::ApplicationCommandInteraction,
+    keys,
+    registry::{BotCommand, CommandFuture},
+    resolver::ApplicationCommandInteractionChatInputDataResolver,
+    response::BotResponse,
+};
+
+const COMMANDS_PER_PAGE: usize = 5;
+
+pub struct Help;
+
+impl BotCommand for Help {
+    type Resolver = ApplicationCommandInteractionChatInputDataResolver;
+
+    fn schema(&self) -> Command {
+        CommandBuilder::new("help", "list every command", CommandType::ChatInput)
+            .integration_types(ALL_INTEGRATIONS)
+            .contexts(ALL_CONTEXTS)
+            .build()
+    }
+
+    fn command<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<'_, '_, Self::Resolver>,
+        context: Arc<BotContext>,
+    ) -> CommandFuture<'a> {
+        Box::pin(command(interaction, context))
+    }
+}
+
+pub async fn command(
+    interaction: &mut ApplicationCommandInteraction<
+        '_,
+        '_,
+        ApplicationCommandInteractionChatInputDataResolver,
+    >,
+    context: Arc<BotContext>,
+) -> BotResult<Option<BotResponse>> {
+    // the pages are posted publicly
+    let locale = interaction.public_locale();
+    let command_lines: Vec<String> = context
+        .commands
+        .schemas()
+        .iter()
+        .map(|schema| {
+            let name = t!(format!("commands.{}.name", schema.name), locale = locale);
+
+            match schema.kind {
+                CommandType::ChatInput => tf!(
+                    keys::commands::help::CHAT_INPUT,
+                    locale = &locale,
+                    name = name,
+                    description = t!(
+                        format!("commands.{}.description", schema.name),
+                        locale = locale
+                    )
+                ),
+                _ => tf!(
+                    keys::commands::help::CONTEXT_MENU,
+                    locale = &locale,
+                    name = name
+                ),
+            }
+        })
+        .collect();
+
+    let pages = command_lines
+        .chunks(COMMANDS_PER_PAGE)
+        .map(|lines| lines.join("\n"))
+        .collect();
+
+    components::paginator::paginate(interaction, context, pages).await?;
+
+    Ok(None)
+}
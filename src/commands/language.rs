@@ -0,0 +1,235 @@
+use std::{sync::Arc, time::Duration};
+
+use twilight_model::{
+    application::command::{Command, CommandOptionChoice, CommandOptionChoiceValue, CommandType},
+    channel::message::MessageFlags,
+    guild::Permissions,
+};
+use twilight_util::builder::{
+    InteractionResponseDataBuilder,
+    command::{CommandBuilder, SubCommandBuilder},
+};
+
+use crate::{
+    commands::{ALL_CONTEXTS, ALL_INTEGRATIONS, MAX_AUTOCOMPLETE_CHOICES},
+    components::confirm,
+    context::BotContext,
+    error::{BotError, BotResult},
+    interaction::ApplicationCommandInteraction,
+    keys,
+    options::CommandOptions,
+    registry::{AutocompleteFuture, BotCommand, CommandFuture},
+    resolver::ApplicationCommandInteractionChatInputDataResolver,
+    response::BotResponse,
+};
+
+/// how long the managers of a guild get to confirm changing its language
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(CommandOptions)]
+pub struct LanguageOptions {
+    #[option(
+        description = "the language to use; leave out to follow the language of discord again",
+        autocomplete
+    )]
+    locale: Option<String>,
+}
+
+/// lets users, and the managers of a guild for the guild, pick the language of the bot instead
+/// of the one their discord client or guild is set to
+pub struct Language;
+
+impl BotCommand for Language {
+    type Resolver = ApplicationCommandInteractionChatInputDataResolver;
+
+    fn schema(&self) -> Command {
+        let subcommand = |name, description| {
+            LanguageOptions::options()
+                .into_iter()
+                .fold(
+                    SubCommandBuilder::new(name, description),
+                    SubCommandBuilder::option,
+                )
+                .build()
+        };
+
+        CommandBuilder::new(
+            "language",
+            "choose the language of the bot",
+            CommandType::ChatInput,
+        )
+        .option(subcommand("user", "the language the bot uses with you"))
+        .option(subcommand(
+            "server",
+            "the language the bot uses for everyone in this server",
+        ))
+        .integration_types(ALL_INTEGRATIONS)
+        .contexts(ALL_CONTEXTS)
+        .build()
+    }
+
+    fn command<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<'_, '_, Self::Resolver>,
+        context: Arc<BotContext>,
+    ) -> CommandFuture<'a> {
+        Box::pin(command(interaction, context))
+    }
+
+    fn autocomplete<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<'_, '_, Self::Resolver>,
+        _: Arc<BotContext>,
+    ) -> AutocompleteFuture<'a> {
+        Box::pin(suggest_locales(interaction))
+    }
+}
+
+pub async fn command(
+    interaction: &mut ApplicationCommandInteraction<
+        '_,
+        '_,
+        ApplicationCommandInteractionChatInputDataResolver,
+    >,
+    context: Arc<BotContext>,
+) -> BotResult<Option<BotResponse>> {
+    let options = LanguageOptions::from_resolver(&interaction.data_resolver)?;
+
+    if let Some(locale) = &options.locale
+        && !available_locales!().contains(&locale.as_str())
+    {
+        return Err(user_error!(
+            keys::commands::language::errors::UNKNOWN_LOCALE,
+            locale = locale
+        ));
+    }
+
+    let language = options
+        .locale
+        .as_deref()
+        .map(|locale| tf!(keys::LOCALE_NAME, locale = locale));
+    let event_interaction = &interaction.application_interaction.event_interaction;
+
+    let is_server = match interaction.data_resolver.subcommand.as_deref() {
+        Some("server") => {
+            let Some(guild_id) = event_interaction.guild_id else {
+                return Err(precondition_error!(
+                    keys::commands::language::errors::GUILD_ONLY
+                ));
+            };
+
+            let can_manage_guild = event_interaction
+                .member
+                .as_ref()
+                .and_then(|member| member.permissions)
+                .is_some_and(|permissions| permissions.contains(Permissions::MANAGE_GUILD));
+
+            if !can_manage_guild {
+                return Err(precondition_error!(
+                    keys::commands::language::errors::MANAGE_GUILD
+                ));
+            }
+
+            // it changes the language for everyone, so it's asked about in public first
+            let public_locale = interaction.public_locale();
+            let prompt = match &language {
+                Some(language) => tf!(
+                    keys::commands::language::server::CONFIRM_SET,
+                    locale = &public_locale,
+                    language = language
+                ),
+                None => tf!(
+                    keys::commands::language::server::CONFIRM_RESET,
+                    locale = &public_locale
+                ),
+            };
+
+            if confirm::confirm(interaction, context.clone(), prompt, CONFIRM_TIMEOUT).await?
+                != Some(true)
+            {
+                return Ok(Some(ephemeral(tl!(
+                    interaction,
+                    keys::commands::language::server::CANCELLED
+                ))));
+            }
+
+            context
+                .locale_preferences
+                .set_guild(guild_id, options.locale.clone())
+                .await?;
+            interaction.application_interaction.preferred_guild_locale = options.locale.clone();
+
+            true
+        }
+        _ => {
+            let Some(user_id) = event_interaction.author_id() else {
+                return Err(BotError::internal("interaction without an author"));
+            };
+
+            context
+                .locale_preferences
+                .set_user(user_id, options.locale.clone())
+                .await?;
+            // so that the reply is already in the language that was picked
+            interaction.application_interaction.preferred_locale = options.locale.clone();
+
+            false
+        }
+    };
+
+    let content = match (is_server, language) {
+        (false, Some(language)) => tl!(
+            interaction,
+            keys::commands::language::user::SET,
+            language = language
+        ),
+        (false, None) => tl!(interaction, keys::commands::language::user::RESET),
+        (true, Some(language)) => {
+            tl!(
+                interaction,
+                keys::commands::language::server::SET,
+                language = language
+            )
+        }
+        (true, None) => tl!(interaction, keys::commands::language::server::RESET),
+    };
+
+    Ok(Some(ephemeral(content)))
+}
+
+fn ephemeral(content: String) -> BotResponse {
+    BotResponse::ResponseData(
+        InteractionResponseDataBuilder::new()
+            .content(content)
+            .flags(MessageFlags::EPHEMERAL)
+            .build(),
+    )
+}
+
+async fn suggest_locales(
+    interaction: &mut ApplicationCommandInteraction<
+        '_,
+        '_,
+        ApplicationCommandInteractionChatInputDataResolver,
+    >,
+) -> BotResult<Vec<CommandOptionChoice>> {
+    let typed = interaction
+        .data_resolver
+        .get_focused_option()
+        .map(|(_, value)| value.trim().to_lowercase())
+        .unwrap_or_default();
+
+    Ok(available_locales!()
+        .into_iter()
+        .map(|locale| (locale, tf!(keys::LOCALE_NAME, locale = locale)))
+        .filter(|(locale, name)| {
+            locale.to_lowercase().contains(&typed) || name.to_lowercase().contains(&typed)
+        })
+        .take(MAX_AUTOCOMPLETE_CHOICES)
+        .map(|(locale, name)| CommandOptionChoice {
+            name: format!("{name} ({locale})"),
+            name_localizations: None,
+            value: CommandOptionChoiceValue::String(locale.to_string()),
+        })
+        .collect())
+}
@@ -1,13 +1,17 @@
 use twilight_model::{
-    application::{command::Command, interaction::InteractionContextType},
-    oauth::ApplicationIntegrationType,
+    application::interaction::InteractionContextType, oauth::ApplicationIntegrationType,
 };
 
+use crate::{config::Features, registry::CommandRegistry};
+
 pub mod debug;
 
 pub mod greet;
+pub mod help;
+pub mod language;
 pub mod ping;
 pub mod roll;
+pub mod say;
 
 pub mod echo;
 
@@ -22,14 +26,22 @@ pub const ALL_INTEGRATIONS: [ApplicationIntegrationType; 2] = [
     ApplicationIntegrationType::UserInstall,
 ];
 
-pub fn command_schemas() -> Vec<Command> {
-    vec![
-        ping::schema(),
-        roll::schema(),
-        echo::schema(),
-        greet::schema(),
-    ]
-    .into_iter()
-    .chain(debug::command_schemas())
-    .collect()
+/// the most choices discord accepts in an autocomplete response
+pub const MAX_AUTOCOMPLETE_CHOICES: usize = 25;
+
+pub fn command_registry(features: &Features) -> CommandRegistry {
+    let mut registry = CommandRegistry::new()
+        .register(help::Help)
+        .register(language::Language)
+        .register(ping::Ping)
+        .register(roll::Roll::default())
+        .register(say::Say)
+        .register(echo::Echo)
+        .register(greet::Greet);
+
+    if features.debug_commands {
+        registry = debug::register(registry);
+    }
+
+    registry
 }
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use twilight_model::{
     application::command::{Command, CommandType},
     channel::message::MessageFlags,
@@ -7,17 +9,33 @@ use twilight_util::builder::{InteractionResponseDataBuilder, command::CommandBui
 use crate::{
     commands::{ALL_CONTEXTS, ALL_INTEGRATIONS},
     components,
+    context::BotContext,
     error::BotResult,
     interaction::ApplicationCommandInteraction,
-    resolver::ApplicationCommandInteractionChatInputDataResolver,
+    registry::{BotCommand, CommandFuture},
+    resolver::{ApplicationCommandInteractionChatInputDataResolver, InteractionDataResolver},
     response::BotResponse,
 };
 
-pub fn schema() -> Command {
-    CommandBuilder::new("ping", "ping", CommandType::ChatInput)
-        .integration_types(ALL_INTEGRATIONS)
-        .contexts(ALL_CONTEXTS)
-        .build()
+pub struct Ping;
+
+impl BotCommand for Ping {
+    type Resolver = ApplicationCommandInteractionChatInputDataResolver;
+
+    fn schema(&self) -> Command {
+        CommandBuilder::new("ping", "ping", CommandType::ChatInput)
+            .integration_types(ALL_INTEGRATIONS)
+            .contexts(ALL_CONTEXTS)
+            .build()
+    }
+
+    fn command<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<'_, '_, Self::Resolver>,
+        _: Arc<BotContext>,
+    ) -> CommandFuture<'a> {
+        Box::pin(command(interaction))
+    }
 }
 
 pub async fn command(
@@ -31,6 +49,16 @@ pub async fn command(
 
     interaction.defer_reply(MessageFlags::empty()).await?;
 
+    respond_with_latency(interaction, time).await?;
+
+    Ok(None)
+}
+
+/// expects the interaction to have been deferred right after `time` was taken
+pub async fn respond_with_latency<R: InteractionDataResolver>(
+    interaction: &mut ApplicationCommandInteraction<'_, '_, R>,
+    time: std::time::Instant,
+) -> BotResult<()> {
     let one_way_trip_time = time.elapsed().as_millis();
 
     let _ = interaction.get_response().await; // obtain the original message (or try to)
@@ -43,12 +71,12 @@ pub async fn command(
                 .components([components::ping::component(
                     round_trip_time,
                     one_way_trip_time,
-                    interaction.locale(),
+                    interaction.public_locale(),
                 )])
                 .flags(MessageFlags::IS_COMPONENTS_V2)
                 .build(),
         )
         .await?;
 
-    Ok(None)
+    Ok(())
 }
@@ -1,54 +1,168 @@
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
 
 use rand::RngExt;
-use twilight_model::application::command::{Command, CommandType};
-use twilight_util::builder::command::{CommandBuilder, StringBuilder};
+use tokio::sync::Mutex;
+use twilight_model::{
+    application::command::{Command, CommandOptionChoice, CommandOptionChoiceValue, CommandType},
+    id::{Id, marker::UserMarker},
+};
+use twilight_util::builder::command::CommandBuilder;
 
 use crate::{
-    commands::{ALL_CONTEXTS, ALL_INTEGRATIONS},
+    commands::{ALL_CONTEXTS, ALL_INTEGRATIONS, MAX_AUTOCOMPLETE_CHOICES},
     context::BotContext,
     error::BotResult,
     interaction::ApplicationCommandInteraction,
+    keys,
+    options::CommandOptions,
+    registry::{AutocompleteFuture, BotCommand, CommandFuture},
     resolver::ApplicationCommandInteractionChatInputDataResolver,
     response::BotResponse,
 };
 
-pub fn schema() -> Command {
-    CommandBuilder::new("roll", "roll from a given range", CommandType::ChatInput)
-        .option(
-            StringBuilder::new("range", "the range to roll from (form of min..max)")
-                .required(true)
-                .build(),
-        )
-        .integration_types(ALL_INTEGRATIONS)
-        .contexts(ALL_CONTEXTS)
-        .build()
+const PRESET_RANGES: [&str; 4] = ["1..6", "1..20", "1..100", "0..1"];
+const SAVED_RANGES_PER_USER: usize = 10;
+
+#[derive(CommandOptions)]
+pub struct RollOptions {
+    #[option(
+        description = "the range to roll from (form of min..max)",
+        autocomplete
+    )]
+    range: String,
 }
 
-pub async fn command(
-    interaction: &mut ApplicationCommandInteraction<
-        '_,
-        '_,
-        ApplicationCommandInteractionChatInputDataResolver,
-    >,
-    context: Arc<BotContext>,
-) -> BotResult<Option<BotResponse>> {
-    let range_string = interaction
-        .data_resolver
-        .get_string_option("range")
-        .ok_or(tl!(interaction, "errors.option.missing", name = "range"))?;
-    let (min, max): (i64, i64) = {
-        let (left, right) = range_string
+/// remembers the ranges each user rolled from (most recent first) so that they can be suggested
+/// back to them while typing
+#[derive(Default)]
+pub struct Roll {
+    saved_ranges: Mutex<HashMap<Id<UserMarker>, VecDeque<String>>>,
+}
+
+impl BotCommand for Roll {
+    type Resolver = ApplicationCommandInteractionChatInputDataResolver;
+
+    fn schema(&self) -> Command {
+        RollOptions::options()
+            .into_iter()
+            .fold(
+                CommandBuilder::new("roll", "roll from a given range", CommandType::ChatInput),
+                CommandBuilder::option,
+            )
+            .integration_types(ALL_INTEGRATIONS)
+            .contexts(ALL_CONTEXTS)
+            .build()
+    }
+
+    fn command<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<'_, '_, Self::Resolver>,
+        context: Arc<BotContext>,
+    ) -> CommandFuture<'a> {
+        Box::pin(self.roll(interaction, context))
+    }
+
+    fn autocomplete<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<'_, '_, Self::Resolver>,
+        _: Arc<BotContext>,
+    ) -> AutocompleteFuture<'a> {
+        Box::pin(self.suggest_ranges(interaction))
+    }
+}
+
+impl Roll {
+    async fn roll(
+        &self,
+        interaction: &mut ApplicationCommandInteraction<
+            '_,
+            '_,
+            ApplicationCommandInteractionChatInputDataResolver,
+        >,
+        context: Arc<BotContext>,
+    ) -> BotResult<Option<BotResponse>> {
+        let options = RollOptions::from_resolver(&interaction.data_resolver)?;
+        let (min, max): (i64, i64) = options
+            .range
             .split_once("..")
-            .ok_or(tl!(interaction, "commands.roll.errors.invalid_syntax"))?;
-        (left.trim().parse()?, right.trim().parse()?)
-    };
+            .and_then(|(left, right)| Some((left.trim().parse().ok()?, right.trim().parse().ok()?)))
+            .ok_or_else(|| user_error!(keys::commands::roll::errors::INVALID_SYNTAX))?;
+
+        if min > max {
+            return Err(user_error!(keys::commands::roll::errors::MIN_GT_MAX));
+        }
+
+        if let Some(user_id) = interaction
+            .application_interaction
+            .event_interaction
+            .author_id()
+        {
+            let mut saved_ranges = self.saved_ranges.lock().await;
+            let user_ranges = saved_ranges.entry(user_id).or_default();
+            let range = format!("{min}..{max}");
+
+            user_ranges.retain(|saved_range| *saved_range != range);
+            user_ranges.push_front(range);
+            user_ranges.truncate(SAVED_RANGES_PER_USER);
+        }
 
-    if min > max {
-        Err(tl!(interaction, "commands.roll.errors.min_gt_max"))?
+        let rolled = context.rng.lock().await.random_range(min..=max);
+
+        Ok(Some(BotResponse::Content(rolled.to_string())))
     }
 
-    let rolled = context.rng.lock().await.random_range(min..=max);
+    async fn suggest_ranges(
+        &self,
+        interaction: &mut ApplicationCommandInteraction<
+            '_,
+            '_,
+            ApplicationCommandInteractionChatInputDataResolver,
+        >,
+    ) -> BotResult<Vec<CommandOptionChoice>> {
+        let typed = interaction
+            .data_resolver
+            .get_focused_option()
+            .map(|(_, value)| value.trim())
+            .unwrap_or_default();
+
+        let saved_ranges = match interaction
+            .application_interaction
+            .event_interaction
+            .author_id()
+        {
+            Some(user_id) => self
+                .saved_ranges
+                .lock()
+                .await
+                .get(&user_id)
+                .cloned()
+                .unwrap_or_default(),
+            None => VecDeque::new(),
+        };
+
+        let mut suggestions: Vec<String> = Vec::new();
 
-    Ok(Some(BotResponse::Content(rolled.to_string())))
+        for range in saved_ranges
+            .into_iter()
+            .chain(PRESET_RANGES.map(String::from))
+            .filter(|range| range.starts_with(typed))
+        {
+            if !suggestions.contains(&range) {
+                suggestions.push(range);
+            }
+        }
+
+        Ok(suggestions
+            .into_iter()
+            .take(MAX_AUTOCOMPLETE_CHOICES)
+            .map(|range| CommandOptionChoice {
+                name: range.clone(),
+                name_localizations: None,
+                value: CommandOptionChoiceValue::String(range),
+            })
+            .collect())
+    }
 }
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use twilight_model::{
+    application::command::{Command, CommandType},
+    channel::message::component::TextInputStyle,
+};
+use twilight_util::builder::command::CommandBuilder;
+
+use crate::{
+    commands::{ALL_CONTEXTS, ALL_INTEGRATIONS},
+    context::BotContext,
+    error::BotResult,
+    interaction::ApplicationCommandInteraction,
+    keys,
+    modal::{ModalBuilder, TextInputBuilder},
+    registry::{BotCommand, BotComponent, CommandFuture},
+    resolver::{
+        ApplicationCommandInteractionChatInputDataResolver, CUSTOM_ID_SEPARATOR,
+        ModalSubmitInteractionDataResolver,
+    },
+    response::BotResponse,
+};
+
+const NAMESPACE: &str = "say";
+const MESSAGE_INPUT: &str = "message";
+
+/// asks for the message through a modal so that it can span multiple lines
+pub struct Say;
+
+impl BotCommand for Say {
+    type Resolver = ApplicationCommandInteractionChatInputDataResolver;
+
+    fn schema(&self) -> Command {
+        CommandBuilder::new("say", "say something", CommandType::ChatInput)
+            .integration_types(ALL_INTEGRATIONS)
+            .contexts(ALL_CONTEXTS)
+            .build()
+    }
+
+    fn command<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<'_, '_, Self::Resolver>,
+        _: Arc<BotContext>,
+    ) -> CommandFuture<'a> {
+        Box::pin(command(interaction))
+    }
+}
+
+impl BotComponent for Say {
+    fn namespace(&self) -> &'static str {
+        NAMESPACE
+    }
+
+    fn modal<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<
+            '_,
+            '_,
+            ModalSubmitInteractionDataResolver,
+        >,
+        _: Arc<BotContext>,
+    ) -> CommandFuture<'a> {
+        Box::pin(modal(interaction))
+    }
+}
+
+pub async fn command(
+    interaction: &mut ApplicationCommandInteraction<
+        '_,
+        '_,
+        ApplicationCommandInteractionChatInputDataResolver,
+    >,
+) -> BotResult<Option<BotResponse>> {
+    Ok(Some(
+        ModalBuilder::new(
+            format!("{NAMESPACE}{CUSTOM_ID_SEPARATOR}submit"),
+            tl!(interaction, keys::commands::say::modal::TITLE),
+        )
+        .text_input(
+            TextInputBuilder::new(
+                MESSAGE_INPUT,
+                tl!(interaction, keys::commands::say::modal::MESSAGE),
+                TextInputStyle::Paragraph,
+            )
+            .max_length(2000)
+            .required(true),
+        )
+        .into(),
+    ))
+}
+
+pub async fn modal(
+    interaction: &mut ApplicationCommandInteraction<'_, '_, ModalSubmitInteractionDataResolver>,
+) -> BotResult<Option<BotResponse>> {
+    let message = interaction
+        .data_resolver
+        .get_text_input(MESSAGE_INPUT)
+        .ok_or_else(|| user_error!(keys::errors::modal::MISSING_INPUT, name = MESSAGE_INPUT))?;
+
+    Ok(Some(BotResponse::Content(message.to_string())))
+}
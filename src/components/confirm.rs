@@ -0,0 +1,163 @@
+use std::{sync::Arc, time::Duration};
+
+use twilight_model::channel::message::{
+    Component, MessageFlags,
+    component::{ActionRow, Button, ButtonStyle, Container, TextDisplay},
+};
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::{
+    collector::ComponentFilter,
+    components,
+    context::BotContext,
+    error::BotResult,
+    interaction::ApplicationCommandInteraction,
+    keys,
+    registry::{BotComponent, CommandFuture},
+    resolver::{
+        CUSTOM_ID_SEPARATOR, InteractionDataResolver, MessageComponentInteractionDataResolver,
+    },
+    response::BotResponse,
+};
+
+const NAMESPACE: &str = "confirm";
+const CONFIRM_ACTION: &str = "yes";
+const CANCEL_ACTION: &str = "no";
+
+fn custom_id(action: &str) -> String {
+    format!("{NAMESPACE}{CUSTOM_ID_SEPARATOR}{action}")
+}
+
+fn button(action: &str, label: String, style: ButtonStyle) -> Component {
+    Component::Button(Button {
+        id: None,
+        custom_id: Some(custom_id(action)),
+        disabled: false,
+        emoji: None,
+        label: Some(label),
+        style,
+        url: None,
+        sku_id: None,
+    })
+}
+
+pub fn component(prompt: String, locale: String) -> Component {
+    Component::Container(Container {
+        id: None,
+        accent_color: None,
+        spoiler: None,
+        components: [
+            Component::TextDisplay(TextDisplay {
+                id: None,
+                content: prompt,
+            }),
+            Component::ActionRow(ActionRow {
+                id: None,
+                components: [
+                    button(
+                        CONFIRM_ACTION,
+                        tf!(keys::components::confirm::CONFIRM, locale = &locale),
+                        ButtonStyle::Success,
+                    ),
+                    button(
+                        CANCEL_ACTION,
+                        tf!(keys::components::confirm::CANCEL, locale = &locale),
+                        ButtonStyle::Danger,
+                    ),
+                ]
+                .into(),
+            }),
+        ]
+        .into(),
+    })
+}
+
+/// responds to the interaction with the prompt and waits for the invoking user to press one of
+/// its buttons; `None` when they didn't within the timeout, after which the buttons are disabled
+pub async fn confirm<R: InteractionDataResolver>(
+    interaction: &mut ApplicationCommandInteraction<'_, '_, R>,
+    context: Arc<BotContext>,
+    prompt: String,
+    timeout: Duration,
+) -> BotResult<Option<bool>> {
+    let mut prompt_component = component(prompt, interaction.public_locale());
+    let prompt_data = InteractionResponseDataBuilder::new()
+        .components([prompt_component.clone()])
+        .flags(MessageFlags::IS_COMPONENTS_V2)
+        .build();
+
+    if interaction.application_interaction.is_deferred {
+        interaction
+            .update_response_from_response_data(prompt_data)
+            .await?;
+    } else {
+        interaction
+            .reply(BotResponse::ResponseData(prompt_data))
+            .await?;
+    }
+
+    let message = interaction.get_response().await?.model().await?;
+    let mut filter = ComponentFilter::new()
+        .message(message.id)
+        .custom_id_prefix(format!("{NAMESPACE}{CUSTOM_ID_SEPARATOR}"));
+
+    if let Some(user_id) = interaction
+        .application_interaction
+        .event_interaction
+        .author_id()
+    {
+        filter = filter.user(user_id);
+    }
+
+    components::disable_interactive(std::slice::from_mut(&mut prompt_component));
+
+    let disabled_data = InteractionResponseDataBuilder::new()
+        .components([prompt_component])
+        .flags(MessageFlags::IS_COMPONENTS_V2)
+        .build();
+
+    let Some((mut collected_interaction, data_resolver)) = context
+        .collectors
+        .next_component(&context, filter, timeout)
+        .await
+    else {
+        interaction
+            .update_response_from_response_data(disabled_data)
+            .await?;
+
+        return Ok(None);
+    };
+
+    collected_interaction
+        .update_message(BotResponse::ResponseData(disabled_data))
+        .await?;
+
+    Ok(Some(data_resolver.custom_id == custom_id(CONFIRM_ACTION)))
+}
+
+/// only receives the presses that no `confirm` call is waiting for, i.e. those of other users or
+/// of prompts that already timed out
+pub struct Confirm;
+
+impl BotComponent for Confirm {
+    fn namespace(&self) -> &'static str {
+        NAMESPACE
+    }
+
+    fn component<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<
+            '_,
+            '_,
+            MessageComponentInteractionDataResolver,
+        >,
+        _: Arc<BotContext>,
+    ) -> CommandFuture<'a> {
+        Box::pin(async move {
+            Ok(Some(BotResponse::Error(tl!(
+                interaction,
+                keys::components::confirm::UNAVAILABLE
+            ))))
+        })
+    }
+}
@@ -6,6 +6,8 @@ use twilight_model::{
     id::{Id, marker::InteractionMarker},
 };
 
+use crate::keys;
+
 pub fn component(
     error_string: String,
     interaction_id: Id<InteractionMarker>,
@@ -17,13 +19,12 @@ pub fn component(
         spoiler: None,
         components: [Component::TextDisplay(TextDisplay {
             id: None,
-            content: t!(
-                "components.error.content",
+            content: tf!(
+                keys::components::error::CONTENT,
+                locale = &locale,
                 error = error_string,
-                interaction_id = interaction_id,
-                locale = locale
-            )
-            .into(),
+                interaction_id = interaction_id.to_string()
+            ),
         })]
         .into(),
     })
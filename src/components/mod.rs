@@ -1,2 +1,34 @@
+use twilight_model::channel::message::Component;
+
+use crate::{commands, registry::ComponentRegistry};
+
+pub mod confirm;
 pub mod error;
+pub mod paginator;
 pub mod ping;
+
+pub fn component_registry() -> ComponentRegistry {
+    ComponentRegistry::new()
+        .register(ping::Ping)
+        .register(confirm::Confirm)
+        .register(paginator::Paginator)
+        .register(commands::say::Say)
+}
+
+/// disables every button and select menu, however deeply nested, e.g. once nothing is waiting
+/// for them to be used anymore
+pub fn disable_interactive(components: &mut [Component]) {
+    for component in components {
+        match component {
+            Component::Button(button) => button.disabled = true,
+            Component::SelectMenu(select_menu) => select_menu.disabled = true,
+            Component::ActionRow(action_row) => disable_interactive(&mut action_row.components),
+            Component::Container(container) => disable_interactive(&mut container.components),
+            Component::Section(section) => {
+                disable_interactive(&mut section.components);
+                disable_interactive(std::slice::from_mut(section.accessory.as_mut()));
+            }
+            _ => (),
+        }
+    }
+}
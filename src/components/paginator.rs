@@ -0,0 +1,266 @@
+use std::{sync::Arc, time::Duration};
+
+use twilight_model::channel::message::{
+    Component, MessageFlags,
+    component::{ActionRow, Button, ButtonStyle, Container, TextDisplay},
+};
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::{
+    collector::ComponentFilter,
+    components,
+    context::BotContext,
+    custom_id::{CustomIdCodec, CustomIdReader, CustomIdState, CustomIdWriter},
+    error::{BotError, BotResult},
+    interaction::ApplicationCommandInteraction,
+    keys,
+    registry::{BotComponent, CommandFuture},
+    resolver::{
+        CUSTOM_ID_SEPARATOR, InteractionDataResolver, MessageComponentInteractionDataResolver,
+    },
+    response::BotResponse,
+};
+
+const NAMESPACE: &str = "paginator";
+/// how long the buttons stay usable after the last time one of them was pressed
+const INACTIVITY_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, Copy)]
+enum PageButton {
+    First,
+    Previous,
+    Next,
+    Last,
+}
+
+impl PageButton {
+    const ALL: [PageButton; 4] = [
+        PageButton::First,
+        PageButton::Previous,
+        PageButton::Next,
+        PageButton::Last,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            PageButton::First => "⏮",
+            PageButton::Previous => "◀",
+            PageButton::Next => "▶",
+            PageButton::Last => "⏭",
+        }
+    }
+
+    fn target_page(&self, page: usize, page_count: usize) -> usize {
+        match self {
+            PageButton::First => 0,
+            PageButton::Previous => page.saturating_sub(1),
+            PageButton::Next => (page + 1).min(page_count - 1),
+            PageButton::Last => page_count - 1,
+        }
+    }
+}
+
+/// what a page button carries: the page it leads to, along with which button it is so that two
+/// buttons leading to the same page still get different custom_ids
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PageState {
+    button: u64,
+    page: u64,
+}
+
+impl CustomIdState for PageState {
+    const VERSION: u8 = 1;
+
+    fn encode(&self, writer: &mut CustomIdWriter) {
+        writer.u64(self.button).u64(self.page);
+    }
+
+    fn decode(reader: &mut CustomIdReader<'_>) -> Option<Self> {
+        Some(Self {
+            button: reader.u64()?,
+            page: reader.u64()?,
+        })
+    }
+}
+
+pub fn component(
+    custom_ids: &CustomIdCodec,
+    content: String,
+    page_indicator: String,
+    page: usize,
+    page_count: usize,
+) -> BotResult<Component> {
+    let mut buttons = Vec::with_capacity(PageButton::ALL.len());
+
+    for button in PageButton::ALL {
+        let target_page = button.target_page(page, page_count);
+        let state = PageState {
+            button: button as u64,
+            page: target_page as u64,
+        };
+
+        buttons.push(Component::Button(Button {
+            id: None,
+            custom_id: Some(custom_ids.encode(NAMESPACE, &state)?),
+            disabled: target_page == page,
+            emoji: None,
+            label: Some(button.label().to_string()),
+            style: ButtonStyle::Secondary,
+            url: None,
+            sku_id: None,
+        }));
+    }
+
+    Ok(Component::Container(Container {
+        id: None,
+        accent_color: None,
+        spoiler: None,
+        components: [
+            Component::TextDisplay(TextDisplay { id: None, content }),
+            Component::TextDisplay(TextDisplay {
+                id: None,
+                content: page_indicator,
+            }),
+            Component::ActionRow(ActionRow {
+                id: None,
+                components: buttons,
+            }),
+        ]
+        .into(),
+    }))
+}
+
+fn page_component<R: InteractionDataResolver>(
+    interaction: &ApplicationCommandInteraction<'_, '_, R>,
+    custom_ids: &CustomIdCodec,
+    pages: &[String],
+    page: usize,
+) -> BotResult<Component> {
+    component(
+        custom_ids,
+        pages[page].clone(),
+        tf!(
+            keys::components::paginator::PAGE,
+            locale = &interaction.public_locale(),
+            page = page + 1,
+            pages = pages.len()
+        ),
+        page,
+        pages.len(),
+    )
+}
+
+/// responds to the interaction with the first page and lets the invoking user flip through the
+/// rest until they stop pressing buttons for a while, after which the buttons are disabled
+pub async fn paginate<R: InteractionDataResolver>(
+    interaction: &mut ApplicationCommandInteraction<'_, '_, R>,
+    context: Arc<BotContext>,
+    pages: Vec<String>,
+) -> BotResult<()> {
+    if pages.is_empty() {
+        return Err(BotError::internal("paginate called without any pages"));
+    }
+
+    let page_count = pages.len();
+    let page_data = |component: Component| {
+        InteractionResponseDataBuilder::new()
+            .components([component])
+            .flags(MessageFlags::IS_COMPONENTS_V2)
+            .build()
+    };
+
+    let mut page = 0;
+    let first_page_data = page_data(page_component(
+        interaction,
+        &context.custom_ids,
+        &pages,
+        page,
+    )?);
+
+    if interaction.application_interaction.is_deferred {
+        interaction
+            .update_response_from_response_data(first_page_data)
+            .await?;
+    } else {
+        interaction
+            .reply(BotResponse::ResponseData(first_page_data))
+            .await?;
+    }
+
+    // nothing to flip through, so there's no point in waiting for buttons that are all disabled
+    if page_count == 1 {
+        return Ok(());
+    }
+
+    let message = interaction.get_response().await?.model().await?;
+    let mut filter = ComponentFilter::new()
+        .message(message.id)
+        .custom_id_prefix(format!("{NAMESPACE}{CUSTOM_ID_SEPARATOR}"));
+
+    if let Some(user_id) = interaction
+        .application_interaction
+        .event_interaction
+        .author_id()
+    {
+        filter = filter.user(user_id);
+    }
+
+    while let Some((mut collected_interaction, data_resolver)) = context
+        .collectors
+        .next_component(&context, filter.clone(), INACTIVITY_TIMEOUT)
+        .await
+    {
+        // one that doesn't verify leaves the page as it is
+        if let Ok(state) = context
+            .custom_ids
+            .verify::<PageState>(&data_resolver.custom_id)
+        {
+            page = (state.page as usize).min(page_count - 1);
+        }
+
+        collected_interaction
+            .update_message(BotResponse::ResponseData(page_data(page_component(
+                interaction,
+                &context.custom_ids,
+                &pages,
+                page,
+            )?)))
+            .await?;
+    }
+
+    let mut disabled_component = page_component(interaction, &context.custom_ids, &pages, page)?;
+    components::disable_interactive(std::slice::from_mut(&mut disabled_component));
+
+    interaction
+        .update_response_from_response_data(page_data(disabled_component))
+        .await?;
+
+    Ok(())
+}
+
+/// only receives the presses that no `paginate` call is waiting for, i.e. those of other users
+/// or of paginators that already went inactive
+pub struct Paginator;
+
+impl BotComponent for Paginator {
+    fn namespace(&self) -> &'static str {
+        NAMESPACE
+    }
+
+    fn component<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<
+            '_,
+            '_,
+            MessageComponentInteractionDataResolver,
+        >,
+        _: Arc<BotContext>,
+    ) -> CommandFuture<'a> {
+        Box::pin(async move {
+            Ok(Some(BotResponse::Error(tl!(
+                interaction,
+                keys::components::paginator::UNAVAILABLE
+            ))))
+        })
+    }
+}
@@ -1,23 +1,90 @@
+use std::sync::Arc;
+
 use twilight_model::channel::message::{
     Component,
-    component::{Container, TextDisplay},
+    component::{ActionRow, Button, ButtonStyle, Container, TextDisplay},
+};
+
+use crate::{
+    commands,
+    context::BotContext,
+    error::BotResult,
+    interaction::ApplicationCommandInteraction,
+    keys,
+    registry::{BotComponent, CommandFuture},
+    resolver::{CUSTOM_ID_SEPARATOR, MessageComponentInteractionDataResolver},
+    response::BotResponse,
 };
 
+const NAMESPACE: &str = "ping";
+
 pub fn component(round_trip_time: u128, one_way_trip_time: u128, locale: String) -> Component {
     Component::Container(Container {
         id: None,
         accent_color: None,
         spoiler: None,
-        components: [Component::TextDisplay(TextDisplay {
-            id: None,
-            content: t!(
-                "components.ping.content",
-                round_trip_time = round_trip_time,
-                one_way_trip_time = one_way_trip_time,
-                locale = locale
-            )
-            .into(),
-        })]
+        components: [
+            Component::TextDisplay(TextDisplay {
+                id: None,
+                content: tf!(
+                    keys::components::ping::CONTENT,
+                    locale = &locale,
+                    round_trip_time = round_trip_time,
+                    one_way_trip_time = one_way_trip_time
+                ),
+            }),
+            Component::ActionRow(ActionRow {
+                id: None,
+                components: [Component::Button(Button {
+                    id: None,
+                    custom_id: Some(format!("{NAMESPACE}{CUSTOM_ID_SEPARATOR}refresh")),
+                    disabled: false,
+                    emoji: None,
+                    label: Some(tf!(keys::components::ping::REFRESH, locale = &locale)),
+                    style: ButtonStyle::Secondary,
+                    url: None,
+                    sku_id: None,
+                })]
+                .into(),
+            }),
+        ]
         .into(),
     })
 }
+
+/// measures the latency again when the refresh button is pressed
+pub struct Ping;
+
+impl BotComponent for Ping {
+    fn namespace(&self) -> &'static str {
+        NAMESPACE
+    }
+
+    fn component<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<
+            '_,
+            '_,
+            MessageComponentInteractionDataResolver,
+        >,
+        _: Arc<BotContext>,
+    ) -> CommandFuture<'a> {
+        Box::pin(refresh(interaction))
+    }
+}
+
+async fn refresh(
+    interaction: &mut ApplicationCommandInteraction<
+        '_,
+        '_,
+        MessageComponentInteractionDataResolver,
+    >,
+) -> BotResult<Option<BotResponse>> {
+    let time = std::time::Instant::now();
+
+    interaction.defer_update_message().await?;
+
+    commands::ping::respond_with_latency(interaction, time).await?;
+
+    Ok(None)
+}
@@ -0,0 +1,613 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use clap::{Args, ValueEnum};
+use serde::{Deserialize, Deserializer};
+use tracing_subscriber::EnvFilter;
+use twilight_gateway::Intents;
+use twilight_model::id::{
+    Id,
+    marker::{GuildMarker, UserMarker},
+};
+
+use crate::{localization::DISCORD_LOCALES, logging::LogFormat};
+
+/// read when neither `--config` nor `HOSHI_CONFIG` point elsewhere; it not existing is fine
+pub const DEFAULT_CONFIG_PATH: &str = "hoshi.toml";
+
+/// prefix of the environment variables that override the config file, e.g. `HOSHI_TOKEN`
+pub const ENV_PREFIX: &str = "HOSHI_";
+
+/// what the token used to be read from before the environment variables got their prefix
+const DEPRECATED_TOKEN_VAR: &str = "TOKEN";
+
+#[derive(Deserialize, ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+    Dev,
+    Prod,
+}
+
+impl Profile {
+    /// debug builds are assumed to be run during development
+    fn fallback() -> Self {
+        if cfg!(debug_assertions) {
+            Self::Dev
+        } else {
+            Self::Prod
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Dev => "dev",
+            Self::Prod => "prod",
+        }
+    }
+
+    /// the bottom layer, overridden by everything else; debug commands stay off, since they need
+    /// dev guilds to be registered to
+    fn defaults(self) -> ConfigLayer {
+        let log_level = match self {
+            Self::Dev => "info,hoshi=debug",
+            Self::Prod => "info",
+        };
+
+        ConfigLayer {
+            default_locale: Some(String::from("en-US")),
+            log_level: Some(String::from(log_level)),
+            log_format: Some(LogFormat::default()),
+            session_file: Some(PathBuf::from("sessions.json")),
+            locale_preferences_file: Some(PathBuf::from("locale_preferences.json")),
+            features: FeatureLayer {
+                debug_commands: Some(false),
+                guild_commands: Some(false),
+                clear_guild_commands: Some(false),
+            },
+            ..ConfigLayer::default()
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Deserialize, ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    /// commands that only exist to test the bot, such as `/error`; registered to the dev guilds
+    DebugCommands,
+    /// registers every command to the dev guilds instead of globally, so that schema changes
+    /// show up right away and only there
+    GuildCommands,
+    /// removes the commands from the dev guilds again when the bot shuts down
+    ClearGuildCommands,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Features {
+    pub debug_commands: bool,
+    pub guild_commands: bool,
+    pub clear_guild_commands: bool,
+}
+
+/// the shards this process runs out of the total; `0..2/4` runs shards 0 and 1 of 4
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardRange {
+    pub start: u32,
+    pub end: u32,
+    pub total: u32,
+}
+
+impl FromStr for ShardRange {
+    type Err = String;
+
+    fn from_str(range: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("\"{range}\" is not a shard range like \"0..2/4\"");
+
+        let (range, total) = range.split_once('/').ok_or_else(invalid)?;
+        let (start, end) = range.split_once("..").ok_or_else(invalid)?;
+
+        Ok(Self {
+            start: start.trim().parse().map_err(|_| invalid())?,
+            end: end.trim().parse().map_err(|_| invalid())?,
+            total: total.trim().parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ShardRange {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// flags that override the config file and the environment
+#[derive(Args, Debug, Clone, Default)]
+pub struct ConfigArgs {
+    /// path of the config file [default: hoshi.toml]
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+    #[arg(long, global = true)]
+    pub profile: Option<Profile>,
+    #[arg(long, global = true)]
+    pub log_level: Option<String>,
+    #[arg(long, global = true)]
+    pub log_format: Option<LogFormat>,
+    #[arg(long, global = true)]
+    pub default_locale: Option<String>,
+    #[arg(long = "owner-id", global = true)]
+    pub owner_ids: Vec<Id<UserMarker>>,
+    #[arg(long = "dev-guild-id", global = true)]
+    pub dev_guild_ids: Vec<Id<GuildMarker>>,
+    /// comma separated intent names, e.g. `GUILDS,GUILD_MESSAGES`
+    #[arg(long, value_delimiter = ',', global = true)]
+    pub intents: Option<Vec<String>>,
+    /// e.g. `0..2/4`; every shard discord recommends when not given
+    #[arg(long, global = true)]
+    pub shards: Option<ShardRange>,
+    #[arg(long, global = true)]
+    pub session_file: Option<PathBuf>,
+    #[arg(long, global = true)]
+    pub locale_preferences_file: Option<PathBuf>,
+    #[arg(long, global = true)]
+    pub locale_dir: Option<PathBuf>,
+    #[arg(long = "enable", global = true)]
+    pub enabled_features: Vec<Feature>,
+    #[arg(long = "disable", global = true)]
+    pub disabled_features: Vec<Feature>,
+}
+
+impl ConfigArgs {
+    fn layer(&self) -> ConfigLayer {
+        ConfigLayer {
+            log_level: self.log_level.clone(),
+            log_format: self.log_format,
+            default_locale: self.default_locale.clone(),
+            owner_ids: (!self.owner_ids.is_empty()).then(|| self.owner_ids.clone()),
+            dev_guild_ids: (!self.dev_guild_ids.is_empty()).then(|| self.dev_guild_ids.clone()),
+            intents: self.intents.clone(),
+            shards: self.shards,
+            session_file: self.session_file.clone(),
+            locale_preferences_file: self.locale_preferences_file.clone(),
+            locale_dir: self.locale_dir.clone(),
+            features: FeatureLayer::toggled(&self.enabled_features, &self.disabled_features),
+            ..ConfigLayer::default()
+        }
+    }
+}
+
+/// the environment variables, all prefixed with `HOSHI_`; lists are comma separated
+#[derive(Deserialize, Debug, Default)]
+struct EnvLayer {
+    config: Option<PathBuf>,
+    profile: Option<Profile>,
+    token: Option<String>,
+    custom_id_key: Option<String>,
+    owner_ids: Option<Vec<Id<UserMarker>>>,
+    dev_guild_ids: Option<Vec<Id<GuildMarker>>>,
+    default_locale: Option<String>,
+    log_level: Option<String>,
+    log_format: Option<LogFormat>,
+    intents: Option<Vec<String>>,
+    shards: Option<ShardRange>,
+    session_file: Option<PathBuf>,
+    locale_preferences_file: Option<PathBuf>,
+    locale_dir: Option<PathBuf>,
+    #[serde(default)]
+    enable_features: Vec<Feature>,
+    #[serde(default)]
+    disable_features: Vec<Feature>,
+}
+
+impl EnvLayer {
+    fn layer(self) -> ConfigLayer {
+        ConfigLayer {
+            token: self.token,
+            custom_id_key: self.custom_id_key,
+            owner_ids: self.owner_ids,
+            dev_guild_ids: self.dev_guild_ids,
+            default_locale: self.default_locale,
+            log_level: self.log_level,
+            log_format: self.log_format,
+            intents: self.intents,
+            shards: self.shards,
+            session_file: self.session_file,
+            locale_preferences_file: self.locale_preferences_file,
+            locale_dir: self.locale_dir,
+            locale_fallbacks: None,
+            features: FeatureLayer::toggled(&self.enable_features, &self.disable_features),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+struct FeatureLayer {
+    debug_commands: Option<bool>,
+    guild_commands: Option<bool>,
+    clear_guild_commands: Option<bool>,
+}
+
+impl FeatureLayer {
+    fn toggled(enabled: &[Feature], disabled: &[Feature]) -> Self {
+        let mut layer = Self::default();
+
+        for (features, toggle) in [(enabled, true), (disabled, false)] {
+            for feature in features {
+                match feature {
+                    Feature::DebugCommands => layer.debug_commands = Some(toggle),
+                    Feature::GuildCommands => layer.guild_commands = Some(toggle),
+                    Feature::ClearGuildCommands => layer.clear_guild_commands = Some(toggle),
+                }
+            }
+        }
+
+        layer
+    }
+
+    fn merge(self, over: Self) -> Self {
+        Self {
+            debug_commands: over.debug_commands.or(self.debug_commands),
+            guild_commands: over.guild_commands.or(self.guild_commands),
+            clear_guild_commands: over.clear_guild_commands.or(self.clear_guild_commands),
+        }
+    }
+}
+
+/// one source of configuration; anything it leaves out falls through to the layer below it
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+struct ConfigLayer {
+    token: Option<String>,
+    custom_id_key: Option<String>,
+    owner_ids: Option<Vec<Id<UserMarker>>>,
+    dev_guild_ids: Option<Vec<Id<GuildMarker>>>,
+    default_locale: Option<String>,
+    log_level: Option<String>,
+    log_format: Option<LogFormat>,
+    intents: Option<Vec<String>>,
+    shards: Option<ShardRange>,
+    session_file: Option<PathBuf>,
+    locale_preferences_file: Option<PathBuf>,
+    locale_dir: Option<PathBuf>,
+    locale_fallbacks: Option<BTreeMap<String, Vec<String>>>,
+    #[serde(default)]
+    features: FeatureLayer,
+}
+
+impl ConfigLayer {
+    fn merge(self, over: Self) -> Self {
+        Self {
+            token: over.token.or(self.token),
+            custom_id_key: over.custom_id_key.or(self.custom_id_key),
+            owner_ids: over.owner_ids.or(self.owner_ids),
+            dev_guild_ids: over.dev_guild_ids.or(self.dev_guild_ids),
+            default_locale: over.default_locale.or(self.default_locale),
+            log_level: over.log_level.or(self.log_level),
+            log_format: over.log_format.or(self.log_format),
+            intents: over.intents.or(self.intents),
+            shards: over.shards.or(self.shards),
+            session_file: over.session_file.or(self.session_file),
+            locale_preferences_file: over
+                .locale_preferences_file
+                .or(self.locale_preferences_file),
+            locale_dir: over.locale_dir.or(self.locale_dir),
+            locale_fallbacks: over.locale_fallbacks.or(self.locale_fallbacks),
+            features: self.features.merge(over.features),
+        }
+    }
+}
+
+/// the config file: its top level keys apply to every profile and `[profiles.<name>]` tables
+/// override them for that profile
+#[derive(Debug, Default)]
+struct ConfigFile {
+    profile: Option<Profile>,
+    base: ConfigLayer,
+    profiles: ProfileLayers,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+struct ProfileLayers {
+    dev: Option<ConfigLayer>,
+    prod: Option<ConfigLayer>,
+}
+
+impl ConfigFile {
+    /// a missing file is only an error when it was asked for explicitly
+    fn load(path: &Path, explicit: bool) -> Result<Self, String> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound && !explicit => {
+                return Ok(Self::default());
+            }
+            Err(error) => return Err(format!("unable to read {}: {error}", path.display())),
+        };
+
+        let invalid = |error: toml::de::Error| format!("{}: {error}", path.display());
+
+        let mut table = toml::from_str::<toml::Table>(&contents).map_err(invalid)?;
+
+        let profile = table
+            .remove("profile")
+            .map(toml::Value::try_into)
+            .transpose()
+            .map_err(invalid)?;
+        let profiles = table
+            .remove("profiles")
+            .map(toml::Value::try_into)
+            .transpose()
+            .map_err(invalid)?
+            .unwrap_or_default();
+        let base = toml::Value::Table(table).try_into().map_err(invalid)?;
+
+        Ok(Self {
+            profile,
+            base,
+            profiles,
+        })
+    }
+
+    fn profile_layer(&mut self, profile: Profile) -> ConfigLayer {
+        match profile {
+            Profile::Dev => self.profiles.dev.take(),
+            Profile::Prod => self.profiles.prod.take(),
+        }
+        .unwrap_or_default()
+    }
+}
+
+/// every problem found while loading the config, so that they can all be fixed in one go
+#[derive(Debug)]
+pub struct ConfigError {
+    pub problems: Vec<String>,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid configuration:")?;
+
+        for problem in &self.problems {
+            write!(f, "\n  - {problem}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Clone)]
+pub struct Config {
+    pub profile: Profile,
+    pub token: String,
+    /// signs the state carried in custom_ids; without it a random key is used, which makes the
+    /// components of messages sent before a restart stop working
+    pub custom_id_key: Option<String>,
+    pub owner_ids: Vec<Id<UserMarker>>,
+    pub dev_guild_ids: Vec<Id<GuildMarker>>,
+    /// used when an interaction doesn't come with a locale of its own
+    pub default_locale: String,
+    /// either a plain level (`info`) or full filter directives (`hoshi=debug,twilight_gateway=warn`)
+    pub log_level: String,
+    pub log_format: LogFormat,
+    pub intents: Intents,
+    pub shards: Option<ShardRange>,
+    /// where the gateway sessions are kept between restarts
+    pub session_file: PathBuf,
+    /// where the languages users and guilds picked with `/language` are kept
+    pub locale_preferences_file: PathBuf,
+    /// locale files loaded at runtime on top of the bundled ones and reloaded when they change,
+    /// so that translations can be fixed without a rebuild
+    pub locale_dir: Option<PathBuf>,
+    /// discord locale -> locales to try before falling back to one of the same language
+    pub locale_fallbacks: BTreeMap<String, Vec<String>>,
+    pub features: Features,
+    /// problems that don't stop the bot from starting, logged once logging is set up
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the token and key are left out so that they can't end up in logs
+        f.debug_struct("Config")
+            .field("profile", &self.profile)
+            .field("owner_ids", &self.owner_ids)
+            .field("dev_guild_ids", &self.dev_guild_ids)
+            .field("default_locale", &self.default_locale)
+            .field("log_level", &self.log_level)
+            .field("log_format", &self.log_format)
+            .field("intents", &self.intents)
+            .field("shards", &self.shards)
+            .field("session_file", &self.session_file)
+            .field("locale_preferences_file", &self.locale_preferences_file)
+            .field("locale_dir", &self.locale_dir)
+            .field("locale_fallbacks", &self.locale_fallbacks)
+            .field("features", &self.features)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Config {
+    /// layered from lowest to highest precedence: the defaults of the profile, the config file,
+    /// its section for the profile, `HOSHI_*` environment variables and finally `args`
+    pub fn load(args: &ConfigArgs) -> Result<Self, ConfigError> {
+        Self::load_layers(args, true)
+    }
+
+    /// for commands that never talk to discord, which don't need a token
+    pub fn load_without_token(args: &ConfigArgs) -> Result<Self, ConfigError> {
+        Self::load_layers(args, false)
+    }
+
+    fn load_layers(args: &ConfigArgs, require_token: bool) -> Result<Self, ConfigError> {
+        let mut problems = Vec::new();
+
+        let mut warnings = Vec::new();
+
+        let mut env = envy::prefixed(ENV_PREFIX)
+            .from_env::<EnvLayer>()
+            .unwrap_or_else(|error| {
+                problems.push(format!("environment: {error}"));
+                EnvLayer::default()
+            });
+
+        if env.token.is_none()
+            && let Ok(token) = std::env::var(DEPRECATED_TOKEN_VAR)
+        {
+            env.token = Some(token);
+            warnings.push(format!(
+                "{DEPRECATED_TOKEN_VAR} is deprecated; rename it to {ENV_PREFIX}TOKEN"
+            ));
+        }
+
+        let explicit_path = args.config.clone().or_else(|| env.config.clone());
+        let path = explicit_path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH));
+
+        let mut file = ConfigFile::load(&path, explicit_path.is_some()).unwrap_or_else(|problem| {
+            problems.push(problem);
+            ConfigFile::default()
+        });
+
+        let profile = args
+            .profile
+            .or(env.profile)
+            .or(file.profile)
+            .unwrap_or_else(Profile::fallback);
+
+        let layer = profile
+            .defaults()
+            .merge(std::mem::take(&mut file.base))
+            .merge(file.profile_layer(profile))
+            .merge(env.layer())
+            .merge(args.layer());
+
+        let mut config = Self::resolve(profile, layer, require_token, &mut problems);
+        config.warnings = warnings;
+
+        if problems.is_empty() {
+            Ok(config)
+        } else {
+            Err(ConfigError { problems })
+        }
+    }
+
+    fn resolve(
+        profile: Profile,
+        layer: ConfigLayer,
+        require_token: bool,
+        problems: &mut Vec<String>,
+    ) -> Self {
+        let token = layer.token.unwrap_or_default();
+
+        if require_token && token.trim().is_empty() {
+            problems.push(String::from(
+                "token is not set; set it in the config file or with HOSHI_TOKEN",
+            ));
+        }
+
+        let default_locale = layer.default_locale.unwrap_or_default();
+
+        if !available_locales!().contains(&default_locale.as_str()) {
+            problems.push(format!(
+                "default_locale \"{default_locale}\" is not one of the available locales ({})",
+                available_locales!().join(", ")
+            ));
+        }
+
+        let log_level = layer.log_level.unwrap_or_default();
+
+        if let Err(error) = EnvFilter::try_new(&log_level) {
+            problems.push(format!("log_level \"{log_level}\" is invalid: {error}"));
+        }
+
+        let locale_fallbacks = layer.locale_fallbacks.unwrap_or_default();
+
+        for (locale, fallbacks) in &locale_fallbacks {
+            if !DISCORD_LOCALES.contains(&locale.as_str()) {
+                problems.push(format!(
+                    "locale_fallbacks: \"{locale}\" is not a locale discord supports"
+                ));
+            }
+
+            for fallback in fallbacks {
+                if !available_locales!().contains(&fallback.as_str()) {
+                    problems.push(format!(
+                        "locale_fallbacks: \"{fallback}\" (for \"{locale}\") is not one of the available locales"
+                    ));
+                }
+            }
+        }
+
+        let mut intents = Intents::empty();
+
+        for name in layer.intents.iter().flatten() {
+            match Intents::from_name(&name.trim().to_uppercase()) {
+                Some(intent) => intents |= intent,
+                None => problems.push(format!("intent \"{name}\" does not exist")),
+            }
+        }
+
+        if let Some(shards) = layer.shards
+            && !(shards.start < shards.end && shards.end <= shards.total)
+        {
+            problems.push(format!(
+                "shards {}..{}/{} has to be a non-empty range within the total",
+                shards.start, shards.end, shards.total
+            ));
+        }
+
+        let features = Features {
+            debug_commands: layer.features.debug_commands.unwrap_or_default(),
+            guild_commands: layer.features.guild_commands.unwrap_or_default(),
+            clear_guild_commands: layer.features.clear_guild_commands.unwrap_or_default(),
+        };
+
+        let dev_guild_ids = layer.dev_guild_ids.unwrap_or_default();
+
+        if dev_guild_ids.is_empty()
+            && (features.debug_commands || features.guild_commands || features.clear_guild_commands)
+        {
+            problems.push(String::from(
+                "debug_commands, guild_commands and clear_guild_commands need at least one dev_guild_ids entry",
+            ));
+        }
+
+        Self {
+            profile,
+            token,
+            custom_id_key: layer.custom_id_key,
+            owner_ids: layer.owner_ids.unwrap_or_default(),
+            dev_guild_ids,
+            default_locale,
+            log_level,
+            log_format: layer.log_format.unwrap_or_default(),
+            intents,
+            shards: layer.shards,
+            session_file: layer.session_file.unwrap_or_default(),
+            locale_preferences_file: layer.locale_preferences_file.unwrap_or_default(),
+            locale_dir: layer.locale_dir,
+            locale_fallbacks,
+            features,
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn is_owner(&self, user_id: Id<UserMarker>) -> bool {
+        self.owner_ids.contains(&user_id)
+    }
+}
@@ -1,17 +1,55 @@
 use rand::rngs::StdRng;
 use tokio::sync::Mutex;
 use twilight_http::{Client as HttpClient, client::InteractionClient};
-use twilight_model::oauth::Application as UserApplication;
+use twilight_model::{
+    application::interaction::Interaction as EventInteraction,
+    oauth::Application as UserApplication,
+};
+
+use crate::{
+    collector::ComponentCollectors,
+    config::Config,
+    custom_id::CustomIdCodec,
+    gateway::GatewayStats,
+    interaction::ApplicationInteraction,
+    preferences::LocalePreferences,
+    registry::{CommandRegistry, ComponentRegistry},
+    shutdown::Shutdown,
+};
 
 #[derive(Debug)]
 pub struct BotContext {
+    pub config: Config,
     pub http_client: HttpClient,
     pub user_application: UserApplication,
     pub rng: Mutex<StdRng>,
+    pub commands: CommandRegistry,
+    pub components: ComponentRegistry,
+    pub custom_ids: CustomIdCodec,
+    pub collectors: ComponentCollectors,
+    pub shutdown: Shutdown,
+    pub gateway_stats: GatewayStats,
+    pub locale_preferences: LocalePreferences,
 }
 
 impl BotContext {
     pub fn interaction(&self) -> InteractionClient<'_> {
         self.http_client.interaction(self.user_application.id)
     }
+
+    /// an interaction that resolves its locales with the preferences picked through `/language`
+    pub fn application_interaction(
+        &self,
+        event_interaction: EventInteraction,
+    ) -> ApplicationInteraction<'_> {
+        let user_locale = event_interaction
+            .author_id()
+            .and_then(|user_id| self.locale_preferences.user(user_id));
+        let guild_locale = event_interaction
+            .guild_id
+            .and_then(|guild_id| self.locale_preferences.guild(guild_id));
+
+        ApplicationInteraction::new(event_interaction, self.interaction())
+            .with_preferred_locales(user_locale, guild_locale)
+    }
 }
@@ -0,0 +1,395 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use twilight_model::id::Id;
+
+use crate::{
+    error::{BotError, BotResult},
+    keys,
+    resolver::CUSTOM_ID_SEPARATOR,
+};
+
+/// the longest custom_id discord accepts
+pub const CUSTOM_ID_MAX_LENGTH: usize = 100;
+
+/// bumped whenever the layout of an encoded custom_id changes, independently of the version of
+/// each state
+const CODEC_VERSION: u8 = 1;
+const FIELD_SEPARATOR: char = '.';
+/// bytes of the hmac kept in the custom_id; enough to make forging impractical without eating
+/// most of the length budget
+const SIGNATURE_LENGTH: usize = 12;
+
+/// state carried through the custom_id of a component, e.g. the invoking user or a page number
+pub trait CustomIdState: Sized {
+    /// bumped whenever the encoded fields change so that custom_ids of older messages are
+    /// rejected as stale instead of being decoded into the wrong fields
+    const VERSION: u8;
+
+    fn encode(&self, writer: &mut CustomIdWriter);
+
+    fn decode(reader: &mut CustomIdReader<'_>) -> Option<Self>;
+}
+
+#[derive(Debug, Default)]
+pub struct CustomIdWriter {
+    fields: Vec<String>,
+}
+
+impl CustomIdWriter {
+    pub fn u64(&mut self, value: u64) -> &mut Self {
+        self.fields.push(to_base36(value));
+        self
+    }
+
+    pub fn i64(&mut self, value: i64) -> &mut Self {
+        match value.is_negative() {
+            true => self
+                .fields
+                .push(format!("-{}", to_base36(value.unsigned_abs()))),
+            false => self.fields.push(to_base36(value.unsigned_abs())),
+        }
+        self
+    }
+
+    pub fn bool(&mut self, value: bool) -> &mut Self {
+        self.fields
+            .push(String::from(if value { "1" } else { "0" }));
+        self
+    }
+
+    pub fn id<Marker>(&mut self, id: Id<Marker>) -> &mut Self {
+        self.u64(id.get())
+    }
+
+    pub fn str(&mut self, value: &str) -> &mut Self {
+        self.fields.push(URL_SAFE_NO_PAD.encode(value));
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct CustomIdReader<'a> {
+    fields: std::str::Split<'a, char>,
+}
+
+impl CustomIdReader<'_> {
+    pub fn u64(&mut self) -> Option<u64> {
+        u64::from_str_radix(self.fields.next()?, 36).ok()
+    }
+
+    pub fn i64(&mut self) -> Option<i64> {
+        let field = self.fields.next()?;
+
+        match field.strip_prefix('-') {
+            // the magnitude of `i64::MIN` doesn't fit in an i64, so it's subtracted as a u64
+            Some(magnitude) => 0i64.checked_sub_unsigned(u64::from_str_radix(magnitude, 36).ok()?),
+            None => i64::try_from(u64::from_str_radix(field, 36).ok()?).ok(),
+        }
+    }
+
+    pub fn bool(&mut self) -> Option<bool> {
+        match self.fields.next()? {
+            "1" => Some(true),
+            "0" => Some(false),
+            _ => None,
+        }
+    }
+
+    pub fn id<Marker>(&mut self) -> Option<Id<Marker>> {
+        Id::new_checked(self.u64()?)
+    }
+
+    pub fn str(&mut self) -> Option<String> {
+        String::from_utf8(URL_SAFE_NO_PAD.decode(self.fields.next()?).ok()?).ok()
+    }
+}
+
+fn to_base36(mut value: u64) -> String {
+    const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    let mut digits = Vec::new();
+
+    loop {
+        digits.push(DIGITS[(value % 36) as usize]);
+        value /= 36;
+
+        if value == 0 {
+            break;
+        }
+    }
+
+    digits.reverse();
+
+    String::from_utf8(digits).expect("base36 digits are ascii")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CustomIdError {
+    /// not produced by this codec at all
+    Malformed,
+    /// the signature doesn't match, so it was tampered with or signed with another key
+    Forged,
+    /// signed by us, but for an older layout of the codec or of the state
+    Stale,
+}
+
+impl CustomIdError {
+    /// the error shown to whoever pressed the component
+    pub fn user_error(self) -> BotError {
+        match self {
+            CustomIdError::Malformed | CustomIdError::Forged => {
+                user_error!(keys::errors::custom_id::INVALID)
+            }
+            CustomIdError::Stale => user_error!(keys::errors::custom_id::STALE),
+        }
+    }
+}
+
+/// packs typed state into a custom_id of the form `namespace:payload.signature` so that
+/// component handlers don't have to keep it anywhere; the namespace is left readable for routing
+#[derive(Clone)]
+pub struct CustomIdCodec {
+    mac: Hmac<Sha256>,
+}
+
+impl CustomIdCodec {
+    pub fn new(key: &[u8]) -> Self {
+        Self {
+            mac: Hmac::new_from_slice(key).expect("hmac accepts keys of any length"),
+        }
+    }
+
+    fn sign(&self, signed: &str) -> Hmac<Sha256> {
+        let mut mac = self.mac.clone();
+        mac.update(signed.as_bytes());
+        mac
+    }
+
+    pub fn encode<S: CustomIdState>(&self, namespace: &str, state: &S) -> BotResult<String> {
+        let mut writer = CustomIdWriter::default();
+        state.encode(&mut writer);
+
+        let mut signed = format!(
+            "{namespace}{CUSTOM_ID_SEPARATOR}{CODEC_VERSION}{FIELD_SEPARATOR}{}",
+            S::VERSION
+        );
+
+        for field in writer.fields {
+            signed.push(FIELD_SEPARATOR);
+            signed.push_str(&field);
+        }
+
+        let signature = self.sign(&signed).finalize().into_bytes();
+        let custom_id = format!(
+            "{signed}{FIELD_SEPARATOR}{}",
+            URL_SAFE_NO_PAD.encode(&signature[..SIGNATURE_LENGTH])
+        );
+
+        if custom_id.len() > CUSTOM_ID_MAX_LENGTH {
+            return Err(BotError::internal(format!(
+                "encoded custom_id for \"{namespace}\" is {} characters long, over the limit of {CUSTOM_ID_MAX_LENGTH}",
+                custom_id.len()
+            )));
+        }
+
+        Ok(custom_id)
+    }
+
+    pub fn verify<S: CustomIdState>(&self, custom_id: &str) -> Result<S, CustomIdError> {
+        let (signed, signature) = custom_id
+            .rsplit_once(FIELD_SEPARATOR)
+            .ok_or(CustomIdError::Malformed)?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| CustomIdError::Malformed)?;
+
+        // `verify_truncated_left` compares only as many bytes as it's given, so a shorter
+        // signature would be far easier to guess
+        if signature.len() != SIGNATURE_LENGTH {
+            return Err(CustomIdError::Malformed);
+        }
+
+        self.sign(signed)
+            .verify_truncated_left(&signature)
+            .map_err(|_| CustomIdError::Forged)?;
+
+        let (_, payload) = signed
+            .split_once(CUSTOM_ID_SEPARATOR)
+            .ok_or(CustomIdError::Malformed)?;
+        let mut fields = payload.split(FIELD_SEPARATOR);
+
+        let codec_version = fields.next().and_then(|version| version.parse::<u8>().ok());
+        let state_version = fields.next().and_then(|version| version.parse::<u8>().ok());
+
+        if codec_version != Some(CODEC_VERSION) || state_version != Some(S::VERSION) {
+            return Err(CustomIdError::Stale);
+        }
+
+        let mut reader = CustomIdReader { fields };
+        let state = S::decode(&mut reader).ok_or(CustomIdError::Malformed)?;
+
+        match reader.fields.next() {
+            Some(_) => Err(CustomIdError::Malformed),
+            None => Ok(state),
+        }
+    }
+
+    /// like `verify` but with the rejection turned into an error for the user
+    pub fn decode<S: CustomIdState>(&self, custom_id: &str) -> BotResult<S> {
+        self.verify(custom_id).map_err(CustomIdError::user_error)
+    }
+}
+
+impl std::fmt::Debug for CustomIdCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomIdCodec").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::id::marker::UserMarker;
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct TestState {
+        user_id: Id<UserMarker>,
+        offset: i64,
+        enabled: bool,
+        label: String,
+    }
+
+    impl CustomIdState for TestState {
+        const VERSION: u8 = 1;
+
+        fn encode(&self, writer: &mut CustomIdWriter) {
+            writer
+                .id(self.user_id)
+                .i64(self.offset)
+                .bool(self.enabled)
+                .str(&self.label);
+        }
+
+        fn decode(reader: &mut CustomIdReader<'_>) -> Option<Self> {
+            Some(Self {
+                user_id: reader.id()?,
+                offset: reader.i64()?,
+                enabled: reader.bool()?,
+                label: reader.str()?,
+            })
+        }
+    }
+
+    /// the same fields as `TestState`, but a newer layout of them
+    #[derive(Debug)]
+    struct NewerTestState;
+
+    impl CustomIdState for NewerTestState {
+        const VERSION: u8 = 2;
+
+        fn encode(&self, _: &mut CustomIdWriter) {}
+
+        fn decode(_: &mut CustomIdReader<'_>) -> Option<Self> {
+            Some(Self)
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Integer(i64);
+
+    impl CustomIdState for Integer {
+        const VERSION: u8 = 1;
+
+        fn encode(&self, writer: &mut CustomIdWriter) {
+            writer.i64(self.0);
+        }
+
+        fn decode(reader: &mut CustomIdReader<'_>) -> Option<Self> {
+            reader.i64().map(Self)
+        }
+    }
+
+    fn state() -> TestState {
+        TestState {
+            user_id: Id::new(123_456_789_012_345_678),
+            offset: -42,
+            enabled: true,
+            label: String::from("page 1."),
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let codec = CustomIdCodec::new(b"key");
+        let custom_id = codec.encode("test", &state()).unwrap();
+
+        assert!(custom_id.starts_with("test:"));
+        assert_eq!(codec.verify::<TestState>(&custom_id), Ok(state()));
+    }
+
+    #[test]
+    fn rejects_tampered_and_foreign_custom_ids() {
+        let codec = CustomIdCodec::new(b"key");
+        let custom_id = codec.encode("test", &state()).unwrap();
+
+        let (signed, signature) = custom_id.rsplit_once(FIELD_SEPARATOR).unwrap();
+        let flipped = if signature.starts_with('A') { 'B' } else { 'A' };
+        let tampered_signature = format!("{signed}.{flipped}{}", &signature[1..]);
+        let tampered_payload = custom_id.replacen("1.1.", "1.1.0", 1);
+        let truncated_signature = format!("{signed}.{}", &signature[..2]);
+
+        assert_eq!(
+            codec.verify::<TestState>(&tampered_signature),
+            Err(CustomIdError::Forged)
+        );
+        assert_eq!(
+            codec.verify::<TestState>(&tampered_payload),
+            Err(CustomIdError::Forged)
+        );
+        assert_eq!(
+            CustomIdCodec::new(b"other key").verify::<TestState>(&custom_id),
+            Err(CustomIdError::Forged)
+        );
+        assert_eq!(
+            codec.verify::<TestState>(&truncated_signature),
+            Err(CustomIdError::Malformed)
+        );
+        assert_eq!(
+            codec.verify::<TestState>("test:nothing"),
+            Err(CustomIdError::Malformed)
+        );
+    }
+
+    #[test]
+    fn rejects_older_state_versions_as_stale() {
+        let codec = CustomIdCodec::new(b"key");
+        let custom_id = codec.encode("test", &state()).unwrap();
+
+        assert_eq!(
+            codec.verify::<NewerTestState>(&custom_id).unwrap_err(),
+            CustomIdError::Stale
+        );
+    }
+
+    #[test]
+    fn refuses_to_exceed_the_length_limit() {
+        let codec = CustomIdCodec::new(b"key");
+        let mut long_state = state();
+        long_state.label = "a".repeat(CUSTOM_ID_MAX_LENGTH);
+
+        assert!(codec.encode("test", &long_state).is_err());
+    }
+
+    #[test]
+    fn round_trips_every_integer_range() {
+        let codec = CustomIdCodec::new(b"key");
+
+        for value in [0, 1, -1, 35, -36, i64::MAX, i64::MIN] {
+            let custom_id = codec.encode("test", &Integer(value)).unwrap();
+
+            assert_eq!(codec.verify::<Integer>(&custom_id), Ok(Integer(value)));
+        }
+    }
+}
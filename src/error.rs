@@ -1,3 +1,76 @@
-pub type BotError = Box<dyn std::error::Error + Send + Sync>;
+use std::fmt;
+
+use crate::{
+    keys,
+    message_format::{MessageArg, ToMessageArg, format_message},
+};
+
+/// how an error is shown to the user depends on who caused it: `User` and `Precondition` are
+/// localized and shown as they are, while `Internal` errors are only logged and the user gets a
+/// generic message along with the interaction id to report
+#[derive(Debug)]
+pub enum BotError {
+    /// the user gave input that can't be acted upon
+    User(LocalizedMessage),
+    /// the user isn't allowed to do this, or it can't be done where they're doing it
+    Precondition(LocalizedMessage),
+    Internal(Box<dyn std::error::Error + Send + Sync>),
+}
 
 pub type BotResult<T> = std::result::Result<T, BotError>;
+
+/// a localization key with the arguments to interpolate into it, localized only once the locale
+/// of whoever is going to read it is known; built with `user_error!` or `precondition_error!`
+#[derive(Debug, Clone)]
+pub struct LocalizedMessage {
+    pub key: &'static str,
+    pub args: Vec<(&'static str, String)>,
+}
+
+impl LocalizedMessage {
+    pub fn localize(&self, locale: &str) -> String {
+        let args: Vec<(&str, MessageArg)> = self
+            .args
+            .iter()
+            .map(|(name, value)| (*name, value.to_message_arg()))
+            .collect();
+
+        format_message(locale, &t!(self.key, locale = locale), &args)
+    }
+}
+
+impl BotError {
+    pub fn internal(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        BotError::Internal(error.into())
+    }
+
+    /// the message to show to the user; internal errors only ever show the generic one
+    pub fn user_message(&self, locale: &str) -> String {
+        match self {
+            BotError::User(message) | BotError::Precondition(message) => message.localize(locale),
+            BotError::Internal(_) => tf!(keys::errors::GENERIC, locale = locale),
+        }
+    }
+
+    pub fn is_internal(&self) -> bool {
+        matches!(self, BotError::Internal(_))
+    }
+}
+
+impl fmt::Display for BotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BotError::User(message) => write!(f, "user error: {}", message.key),
+            BotError::Precondition(message) => write!(f, "precondition failed: {}", message.key),
+            BotError::Internal(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+// `BotError` deliberately doesn't implement `Error` itself so that anything that does can be
+// turned into an internal error with `?`
+impl<E: std::error::Error + Send + Sync + 'static> From<E> for BotError {
+    fn from(value: E) -> Self {
+        BotError::Internal(Box::new(value))
+    }
+}
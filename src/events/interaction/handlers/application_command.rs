@@ -5,11 +5,11 @@ use twilight_model::application::{
 };
 
 use crate::{
-    commands,
     context::BotContext,
-    error::BotResult,
+    error::{BotError, BotResult},
     interaction::{ApplicationCommandInteraction, ApplicationInteraction},
-    match_command_arm,
+    keys,
+    registry::{CommandKind, CommandPath},
     resolver::{
         ApplicationCommandInteractionChatInputDataResolver,
         ApplicationCommandInteractionMessageDataResolver,
@@ -18,73 +18,23 @@ use crate::{
     response::BotResponse,
 };
 
-async fn handle_chat_input_command(
-    interaction: &mut ApplicationCommandInteraction<
-        '_,
-        '_,
-        ApplicationCommandInteractionChatInputDataResolver,
-    >,
+async fn dispatch<R: CommandKind>(
+    interaction: &mut ApplicationCommandInteraction<'_, '_, R>,
+    command_path: CommandPath,
     context: Arc<BotContext>,
 ) -> BotResult<Option<BotResponse>> {
-    let command_name = interaction.data_resolver.command.as_str();
-    let subcommand_group_name = interaction
-        .data_resolver
-        .subcommand_group
-        .as_ref()
-        .map(|s| s.as_str());
-    let subcommand_name = interaction
-        .data_resolver
-        .subcommand
-        .as_ref()
-        .map(|s| s.as_str());
-
-    match (command_name, subcommand_group_name, subcommand_name) {
-        match_command_arm!("ping") => commands::ping::command(interaction).await,
-        match_command_arm!("roll") => commands::roll::command(interaction, context).await,
-
-        #[cfg(debug_assertions)]
-        match_command_arm!("error") => commands::debug::error::command().await,
-
-        _ => Ok(Some(BotResponse::Error(
-            tl!(interaction, "errors.unhandled", x = command_name).into(),
-        ))),
-    }
-}
-
-async fn handle_message_command(
-    interaction: &mut ApplicationCommandInteraction<
-        '_,
-        '_,
-        ApplicationCommandInteractionMessageDataResolver,
-    >,
-    _: Arc<BotContext>,
-) -> BotResult<Option<BotResponse>> {
-    let command_name = interaction.data_resolver.command.as_str();
-
-    match command_name {
-        "echo" => commands::echo::command(interaction).await,
-        _ => Ok(Some(BotResponse::Error(
-            tl!(interaction, "errors.unhandled", x = command_name).into(),
-        ))),
-    }
-}
-
-async fn handle_user_command(
-    interaction: &mut ApplicationCommandInteraction<
-        '_,
-        '_,
-        ApplicationCommandInteractionUserDataResolver,
-    >,
-    _: Arc<BotContext>,
-) -> BotResult<Option<BotResponse>> {
-    let command_name = interaction.data_resolver.command.as_str();
-
-    match command_name {
-        "greet" => commands::greet::command(interaction).await,
-        _ => Ok(Some(BotResponse::Error(
-            tl!(interaction, "errors.unhandled", x = command_name).into(),
-        ))),
-    }
+    tracing::Span::current().record("command", tracing::field::display(&command_path));
+
+    // only reachable when discord still knows about a command that we no longer register
+    let Some(command) = context.commands.get::<R>(&command_path) else {
+        return Ok(Some(BotResponse::Error(tl!(
+            interaction,
+            keys::errors::UNHANDLED,
+            x = command_path.to_string()
+        ))));
+    };
+
+    command.command(interaction, context).await
 }
 
 pub async fn handler(
@@ -98,57 +48,71 @@ pub async fn handler(
                 ApplicationCommandInteractionChatInputDataResolver::from_commmand_data(
                     command_data,
                 );
+            let command_path = CommandPath::new(
+                interaction_data_resolver.command.clone(),
+                interaction_data_resolver.subcommand_group.clone(),
+                interaction_data_resolver.subcommand.clone(),
+            );
             let interaction = &mut ApplicationCommandInteraction::new(
                 application_interaction,
                 interaction_data_resolver,
             );
 
-            handle_chat_input_command(interaction, context).await
+            dispatch(interaction, command_path, context).await
         }
         CommandType::Message => {
             let target_id = command_data
                 .target_id
-                .ok_or("message_data target_id's field is None")?;
+                .ok_or_else(|| BotError::internal("message_data target_id's field is None"))?;
             let target_messasge = command_data
                 .resolved
                 .as_ref()
                 .and_then(|resolved| resolved.messages.get(&target_id.cast()))
-                .ok_or("unable to fetch resolved target_message command_data")?
+                .ok_or_else(|| {
+                    BotError::internal("unable to fetch resolved target_message command_data")
+                })?
                 .clone();
 
             let interaction_data_resolver = ApplicationCommandInteractionMessageDataResolver::new(
                 command_data.name.clone(),
                 target_messasge,
             );
+            let command_path = CommandPath::new(command_data.name, None, None);
             let interaction = &mut ApplicationCommandInteraction::new(
                 application_interaction,
                 interaction_data_resolver,
             );
 
-            handle_message_command(interaction, context).await
+            dispatch(interaction, command_path, context).await
         }
         CommandType::User => {
             let target_id = command_data
                 .target_id
-                .ok_or("message_data target_id's field is None")?;
+                .ok_or_else(|| BotError::internal("message_data target_id's field is None"))?;
             let target_user = command_data
                 .resolved
                 .as_ref()
                 .and_then(|resolved| resolved.users.get(&target_id.cast()))
-                .ok_or("unable to fetch resolved target_message command_data")?
+                .ok_or_else(|| {
+                    BotError::internal("unable to fetch resolved target_message command_data")
+                })?
                 .clone();
 
             let interaction_data_resolver = ApplicationCommandInteractionUserDataResolver::new(
                 command_data.name.clone(),
                 target_user,
             );
+            let command_path = CommandPath::new(command_data.name, None, None);
             let interaction = &mut ApplicationCommandInteraction::new(
                 application_interaction,
                 interaction_data_resolver,
             );
 
-            handle_user_command(interaction, context).await
+            dispatch(interaction, command_path, context).await
         }
-        _ => todo!(),
+        kind => Err(BotError::internal(format!(
+            "unhandled command type {}",
+            u8::from(kind)
+        ))),
     }
 }
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use twilight_model::application::interaction::application_command::CommandData;
+
+use crate::{
+    context::BotContext,
+    error::{BotError, BotResult},
+    interaction::{ApplicationCommandInteraction, ApplicationInteraction},
+    registry::CommandPath,
+    resolver::ApplicationCommandInteractionChatInputDataResolver,
+    response::BotResponse,
+};
+
+/// autocomplete interactions can only be answered with choices, so anything that goes wrong is
+/// returned as an error rather than as a `BotResponse::Error`
+pub async fn handler(
+    application_interaction: &mut ApplicationInteraction<'_>,
+    command_data: Box<CommandData>,
+    context: Arc<BotContext>,
+) -> BotResult<Option<BotResponse>> {
+    let interaction_data_resolver =
+        ApplicationCommandInteractionChatInputDataResolver::from_commmand_data(command_data);
+    let command_path = CommandPath::new(
+        interaction_data_resolver.command.clone(),
+        interaction_data_resolver.subcommand_group.clone(),
+        interaction_data_resolver.subcommand.clone(),
+    );
+
+    tracing::Span::current().record("command", tracing::field::display(&command_path));
+
+    let command = context
+        .commands
+        .get::<ApplicationCommandInteractionChatInputDataResolver>(&command_path)
+        .ok_or_else(|| {
+            BotError::internal(format!(
+                "no command registered for autocomplete of \"{command_path}\""
+            ))
+        })?;
+
+    let interaction =
+        &mut ApplicationCommandInteraction::new(application_interaction, interaction_data_resolver);
+
+    let choices = command.autocomplete(interaction, context).await?;
+
+    interaction.autocomplete(choices).await?;
+
+    Ok(None)
+}
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+
+use crate::{
+    context::BotContext,
+    error::BotResult,
+    interaction::{ApplicationCommandInteraction, ApplicationInteraction},
+    keys,
+    resolver::MessageComponentInteractionDataResolver,
+    response::BotResponse,
+};
+
+pub async fn handler(
+    application_interaction: &mut ApplicationInteraction<'_>,
+    component_data: Box<MessageComponentInteractionData>,
+    context: Arc<BotContext>,
+) -> BotResult<Option<BotResponse>> {
+    let interaction_data_resolver =
+        MessageComponentInteractionDataResolver::from_component_data(*component_data);
+    let interaction =
+        &mut ApplicationCommandInteraction::new(application_interaction, interaction_data_resolver);
+
+    tracing::Span::current().record("custom_id", interaction.data_resolver.custom_id.as_str());
+
+    let Some(component) = context
+        .components
+        .get(interaction.data_resolver.namespace())
+    else {
+        return Ok(Some(BotResponse::Error(tl!(
+            interaction,
+            keys::errors::UNHANDLED,
+            x = interaction.data_resolver.custom_id
+        ))));
+    };
+
+    component.component(interaction, context).await
+}
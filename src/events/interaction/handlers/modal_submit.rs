@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use twilight_model::application::interaction::modal::ModalInteractionData;
+
+use crate::{
+    context::BotContext,
+    error::BotResult,
+    interaction::{ApplicationCommandInteraction, ApplicationInteraction},
+    keys,
+    resolver::ModalSubmitInteractionDataResolver,
+    response::BotResponse,
+};
+
+pub async fn handler(
+    application_interaction: &mut ApplicationInteraction<'_>,
+    modal_data: Box<ModalInteractionData>,
+    context: Arc<BotContext>,
+) -> BotResult<Option<BotResponse>> {
+    let interaction_data_resolver =
+        ModalSubmitInteractionDataResolver::from_modal_data(*modal_data);
+    let interaction =
+        &mut ApplicationCommandInteraction::new(application_interaction, interaction_data_resolver);
+
+    tracing::Span::current().record("custom_id", interaction.data_resolver.custom_id.as_str());
+
+    let Some(component) = context
+        .components
+        .get(interaction.data_resolver.namespace())
+    else {
+        return Ok(Some(BotResponse::Error(tl!(
+            interaction,
+            keys::errors::UNHANDLED,
+            x = interaction.data_resolver.custom_id
+        ))));
+    };
+
+    component.modal(interaction, context).await
+}
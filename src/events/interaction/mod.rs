@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use tracing::{Instrument, debug, error, info_span};
 use twilight_model::{
     application::interaction::{Interaction as EventInteraction, InteractionData, InteractionType},
     channel::message::MessageFlags,
@@ -7,7 +8,7 @@ use twilight_model::{
 use twilight_util::builder::InteractionResponseDataBuilder;
 
 use crate::{
-    components, context::BotContext, error::BotResult, interaction::ApplicationInteraction,
+    components, context::BotContext, error::BotResult, interaction::ApplicationInteraction, keys,
     response::BotResponse,
 };
 
@@ -28,60 +29,106 @@ async fn handle_interaction(
             handlers::application_command::handler(application_interaction, command_data, context)
                 .await
         }
-        _ => Ok(Some(BotResponse::Error(
-            tl!(
-                application_interaction,
-                "errors.unhandled",
-                x = event_kind.kind()
-            )
-            .into(),
-        ))),
+        (
+            InteractionType::ApplicationCommandAutocomplete,
+            Some(InteractionData::ApplicationCommand(command_data)),
+        ) => handlers::autocomplete::handler(application_interaction, command_data, context).await,
+        (
+            InteractionType::MessageComponent,
+            Some(InteractionData::MessageComponent(component_data)),
+        ) => {
+            handlers::message_component::handler(application_interaction, component_data, context)
+                .await
+        }
+        (InteractionType::ModalSubmit, Some(InteractionData::ModalSubmit(modal_data))) => {
+            handlers::modal_submit::handler(application_interaction, modal_data, context).await
+        }
+        _ => Ok(Some(BotResponse::Error(tl!(
+            application_interaction,
+            keys::errors::UNHANDLED,
+            x = event_kind.kind()
+        )))),
     }
 }
 
 pub async fn event(event_interaction: EventInteraction, context: Arc<BotContext>) -> BotResult<()> {
-    let mut application_interaction =
-        ApplicationInteraction::new(event_interaction, context.interaction());
+    let span = info_span!(
+        "interaction",
+        interaction_id = %event_interaction.id,
+        kind = event_interaction.kind.kind(),
+        guild_id = event_interaction.guild_id.map(tracing::field::display),
+        user_id = event_interaction.author_id().map(tracing::field::display),
+        locale = event_interaction.locale.as_deref(),
+        command = tracing::field::Empty,
+        custom_id = tracing::field::Empty,
+    );
 
-    let bot_response_data_option =
-        match handle_interaction(&mut application_interaction, context.clone()).await {
-            Ok(response_data) => response_data,
-            Err(error) => Some(BotResponse::Error(error.to_string())),
-        };
+    respond(event_interaction, context).instrument(span).await
+}
+
+async fn respond(event_interaction: EventInteraction, context: Arc<BotContext>) -> BotResult<()> {
+    let mut application_interaction = context.application_interaction(event_interaction);
+
+    let handle_result = handle_interaction(&mut application_interaction, context.clone()).await;
 
-    match bot_response_data_option {
-        Some(bot_response_data) => {
-            let response_data = match bot_response_data {
-                BotResponse::ResponseData(response_data) => response_data,
-                BotResponse::Content(content) => InteractionResponseDataBuilder::new()
-                    .content(content)
-                    .build(),
-                BotResponse::Error(error_string) => InteractionResponseDataBuilder::new()
-                    .components([components::error::component(
-                        error_string,
-                        application_interaction.event_interaction.id,
-                        application_interaction.locale(),
-                    )])
-                    .flags(MessageFlags::IS_COMPONENTS_V2.union(MessageFlags::EPHEMERAL))
-                    .build(),
-            };
-
-            if application_interaction.is_deferred {
-                application_interaction
-                    .update_response_from_response_data(response_data)
-                    .await?;
-            } else if application_interaction.has_replied {
-                application_interaction
-                    .followup_from_response_data(response_data)
-                    .await?;
+    // discord rejects anything other than choices as the response to an autocomplete
+    if application_interaction.event_interaction.kind
+        == InteractionType::ApplicationCommandAutocomplete
+    {
+        return handle_result.map(|_| ());
+    }
+
+    let bot_response_data_option = match handle_result {
+        Ok(response_data) => response_data,
+        Err(error) => {
+            // only internal errors are worth looking into; the rest are the user's to fix
+            // the interaction span already carries who, where and what
+            if error.is_internal() {
+                error!(?error, "interaction failed");
             } else {
-                application_interaction
-                    .reply(BotResponse::ResponseData(response_data))
-                    .await?;
+                debug!(%error, "interaction rejected");
             }
+
+            Some(BotResponse::Error(
+                error.user_message(&application_interaction.locale()),
+            ))
         }
-        None => (),
     };
 
+    if let Some(bot_response_data) = bot_response_data_option {
+        let response_data = match bot_response_data {
+            // a modal can only be the first response to an interaction; discord rejects it otherwise
+            BotResponse::Modal(modal_data) => {
+                application_interaction.modal(modal_data).await?;
+
+                return Ok(());
+            }
+            BotResponse::ResponseData(response_data) => response_data,
+            BotResponse::Content(content) => BotResponse::content_data(content),
+            BotResponse::Error(error_string) => InteractionResponseDataBuilder::new()
+                .components([components::error::component(
+                    error_string,
+                    application_interaction.event_interaction.id,
+                    application_interaction.locale(),
+                )])
+                .flags(MessageFlags::IS_COMPONENTS_V2.union(MessageFlags::EPHEMERAL))
+                .build(),
+        };
+
+        if application_interaction.is_deferred {
+            application_interaction
+                .update_response_from_response_data(response_data)
+                .await?;
+        } else if application_interaction.has_replied {
+            application_interaction
+                .followup_from_response_data(response_data)
+                .await?;
+        } else {
+            application_interaction
+                .reply(BotResponse::ResponseData(response_data))
+                .await?;
+        }
+    }
+
     Ok(())
 }
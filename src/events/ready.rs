@@ -1,9 +1,11 @@
+use tracing::info;
 use twilight_model::gateway::payload::incoming::Ready as ReadyEvent;
 
 pub fn event(ready_event: ReadyEvent) {
-    println!(
-        "ready event received for {}#{}",
-        ready_event.user.name,
-        ready_event.user.discriminator()
+    info!(
+        user = %format_args!("{}#{}", ready_event.user.name, ready_event.user.discriminator()),
+        shard = ?ready_event.shard,
+        guilds = ready_event.guilds.len(),
+        "ready event received"
     );
 }
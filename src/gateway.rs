@@ -0,0 +1,93 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use twilight_gateway::{
+    CloseFrame,
+    error::{ReceiveMessageError, ReceiveMessageErrorType},
+};
+use twilight_model::gateway::CloseCode;
+
+/// what went wrong while receiving from a shard, classified by whether the shard can carry on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GatewayIssue {
+    /// an event that couldn't be deserialized; it is skipped
+    Deserializing,
+    /// a message that couldn't be decompressed; it is skipped
+    Compression,
+    /// the shard failed to reconnect and keeps retrying with a backoff
+    Reconnect,
+    /// an error twilight added that isn't classified yet; assumed to be recoverable
+    Other,
+    /// discord closed the connection with a code that can't be reconnected from, such as an
+    /// invalid token, invalid shard or disallowed intents
+    FatalClose(CloseCode),
+}
+
+impl GatewayIssue {
+    pub fn from_error(error: &ReceiveMessageError) -> Self {
+        match error.kind() {
+            ReceiveMessageErrorType::Deserializing { .. } => Self::Deserializing,
+            ReceiveMessageErrorType::Compression => Self::Compression,
+            ReceiveMessageErrorType::Reconnect => Self::Reconnect,
+            _ => Self::Other,
+        }
+    }
+
+    /// `None` for close frames the shard reconnects after on its own
+    pub fn from_close(frame: Option<&CloseFrame<'_>>) -> Option<Self> {
+        let close_code = CloseCode::try_from(frame?.code).ok()?;
+
+        (!close_code.can_reconnect()).then_some(Self::FatalClose(close_code))
+    }
+
+    pub fn is_fatal(self) -> bool {
+        matches!(self, Self::FatalClose(_))
+    }
+}
+
+/// counters of the issues that shards ran into since startup
+#[derive(Debug, Default)]
+pub struct GatewayStats {
+    deserializing: AtomicU64,
+    compression: AtomicU64,
+    reconnect: AtomicU64,
+    other: AtomicU64,
+    fatal_closes: AtomicU64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GatewayStatsSnapshot {
+    pub deserializing: u64,
+    pub compression: u64,
+    pub reconnect: u64,
+    pub other: u64,
+    pub fatal_closes: u64,
+}
+
+impl GatewayStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// returns how many times this kind of issue has happened, including this one
+    pub fn record(&self, issue: GatewayIssue) -> u64 {
+        let counter = match issue {
+            GatewayIssue::Deserializing => &self.deserializing,
+            GatewayIssue::Compression => &self.compression,
+            GatewayIssue::Reconnect => &self.reconnect,
+            GatewayIssue::Other => &self.other,
+            GatewayIssue::FatalClose(_) => &self.fatal_closes,
+        };
+
+        counter.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    pub fn snapshot(&self) -> GatewayStatsSnapshot {
+        GatewayStatsSnapshot {
+            deserializing: self.deserializing.load(Ordering::Relaxed),
+            compression: self.compression.load(Ordering::Relaxed),
+            reconnect: self.reconnect.load(Ordering::Relaxed),
+            other: self.other.load(Ordering::Relaxed),
+            fatal_closes: self.fatal_closes.load(Ordering::Relaxed),
+        }
+    }
+}
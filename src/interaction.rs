@@ -5,13 +5,16 @@ use twilight_http::{
     response::marker::EmptyBody,
 };
 use twilight_model::{
-    application::interaction::Interaction as EventInteraction,
+    application::{command::CommandOptionChoice, interaction::Interaction as EventInteraction},
     channel::{Message, message::MessageFlags},
     http::interaction::{InteractionResponse, InteractionResponseData, InteractionResponseType},
 };
 use twilight_util::builder::InteractionResponseDataBuilder;
 
-use crate::{error::BotResult, resolver::InteractionDataResolver, response::BotResponse};
+use crate::{
+    error::BotResult, localization::negotiate_locale, resolver::InteractionDataResolver,
+    response::BotResponse,
+};
 
 #[derive(Debug)]
 pub struct ApplicationInteraction<'client> {
@@ -19,6 +22,10 @@ pub struct ApplicationInteraction<'client> {
     pub interaction_client: InteractionClient<'client>,
     pub has_replied: bool,
     pub is_deferred: bool,
+    /// picked by the user through `/language`; takes precedence over what discord sends
+    pub preferred_locale: Option<String>,
+    /// picked by a manager of the guild through `/language`
+    pub preferred_guild_locale: Option<String>,
 }
 
 impl<'client> ApplicationInteraction<'client> {
@@ -31,9 +38,21 @@ impl<'client> ApplicationInteraction<'client> {
             interaction_client,
             has_replied: false,
             is_deferred: false,
+            preferred_locale: None,
+            preferred_guild_locale: None,
         }
     }
 
+    pub fn with_preferred_locales(
+        mut self,
+        preferred_locale: Option<String>,
+        preferred_guild_locale: Option<String>,
+    ) -> Self {
+        self.preferred_locale = preferred_locale;
+        self.preferred_guild_locale = preferred_guild_locale;
+        self
+    }
+
     pub async fn create_response(
         &self,
         kind: InteractionResponseType,
@@ -46,18 +65,13 @@ impl<'client> ApplicationInteraction<'client> {
                 &self.event_interaction.token,
                 &InteractionResponse {
                     kind,
-                    data: match data_option {
-                        Some(data) => Some(match data {
-                            BotResponse::ResponseData(data) => data,
-                            BotResponse::Content(content) => InteractionResponseDataBuilder::new()
-                                .content(content)
-                                .build(),
-                            BotResponse::Error(error) => {
-                                InteractionResponseDataBuilder::new().content(error).build()
-                            }
-                        }),
-                        None => None,
-                    },
+                    data: data_option.map(|data| match data {
+                        BotResponse::ResponseData(data) | BotResponse::Modal(data) => data,
+                        BotResponse::Content(content) => BotResponse::content_data(content),
+                        BotResponse::Error(error) => {
+                            InteractionResponseDataBuilder::new().content(error).build()
+                        }
+                    }),
                 },
             )
             .await?)
@@ -83,6 +97,65 @@ impl<'client> ApplicationInteraction<'client> {
         Ok(response)
     }
 
+    pub async fn autocomplete(
+        &mut self,
+        choices: Vec<CommandOptionChoice>,
+    ) -> BotResult<Response<EmptyBody>> {
+        let response = self
+            .create_response(
+                InteractionResponseType::ApplicationCommandAutocompleteResult,
+                Some(BotResponse::ResponseData(
+                    InteractionResponseDataBuilder::new()
+                        .choices(choices)
+                        .build(),
+                )),
+            )
+            .await?;
+
+        self.has_replied = true;
+
+        Ok(response)
+    }
+
+    pub async fn modal(
+        &mut self,
+        modal_data: InteractionResponseData,
+    ) -> BotResult<Response<EmptyBody>> {
+        let response = self
+            .create_response(
+                InteractionResponseType::Modal,
+                Some(BotResponse::Modal(modal_data)),
+            )
+            .await?;
+
+        self.has_replied = true;
+
+        Ok(response)
+    }
+
+    /// edits the message the component is attached to as the response to its interaction
+    pub async fn update_message(&mut self, data: BotResponse) -> BotResult<Response<EmptyBody>> {
+        let response = self
+            .create_response(InteractionResponseType::UpdateMessage, Some(data))
+            .await?;
+
+        self.has_replied = true;
+
+        Ok(response)
+    }
+
+    /// acknowledges a component interaction; the message it is attached to can then be edited
+    /// through the `update_response` methods
+    pub async fn defer_update_message(&mut self) -> BotResult<Response<EmptyBody>> {
+        let response = self
+            .create_response(InteractionResponseType::DeferredUpdateMessage, None)
+            .await?;
+
+        self.is_deferred = true;
+
+        Ok(response)
+    }
+
     pub fn followup_builder(&self) -> CreateFollowup<'_> {
         self.interaction_client
             .create_followup(&self.event_interaction.token)
@@ -144,11 +217,28 @@ impl<'client> ApplicationInteraction<'client> {
         Ok(response)
     }
 
+    /// the user's locale; for ephemeral replies and anything else only they see
     pub fn locale(&self) -> String {
-        self.event_interaction
-            .locale
-            .clone()
-            .unwrap_or(String::from("en-US"))
+        negotiate_locale(
+            self.preferred_locale
+                .as_deref()
+                .or(self.event_interaction.locale.as_deref()),
+        )
+    }
+
+    /// the guild's locale, or the user's outside of guilds; for messages everyone in the channel
+    /// sees
+    pub fn public_locale(&self) -> String {
+        if self.event_interaction.guild_id.is_none() {
+            return self.locale();
+        }
+
+        negotiate_locale(
+            self.preferred_guild_locale
+                .as_deref()
+                .or(self.event_interaction.guild_locale.as_deref())
+                .or(self.event_interaction.locale.as_deref()),
+        )
     }
 }
 
@@ -179,6 +269,28 @@ impl<'interaction, 'client, R: InteractionDataResolver>
         self.application_interaction.reply(data).await
     }
 
+    pub async fn autocomplete(
+        &mut self,
+        choices: Vec<CommandOptionChoice>,
+    ) -> BotResult<Response<EmptyBody>> {
+        self.application_interaction.autocomplete(choices).await
+    }
+
+    pub async fn modal(
+        &mut self,
+        modal_data: InteractionResponseData,
+    ) -> BotResult<Response<EmptyBody>> {
+        self.application_interaction.modal(modal_data).await
+    }
+
+    pub async fn update_message(&mut self, data: BotResponse) -> BotResult<Response<EmptyBody>> {
+        self.application_interaction.update_message(data).await
+    }
+
+    pub async fn defer_update_message(&mut self) -> BotResult<Response<EmptyBody>> {
+        self.application_interaction.defer_update_message().await
+    }
+
     pub fn followup_builder(&self) -> CreateFollowup<'_> {
         self.application_interaction.followup_builder()
     }
@@ -217,4 +329,8 @@ impl<'interaction, 'client, R: InteractionDataResolver>
     pub fn locale(&self) -> String {
         self.application_interaction.locale()
     }
+
+    pub fn public_locale(&self) -> String {
+        self.application_interaction.public_locale()
+    }
 }
@@ -0,0 +1,17 @@
+//! typed localization keys generated by `build.rs` from `locales/en-US.yml`, one struct per key
+//! with a `()` field for every argument its message takes
+//!
+//! `tl!` and `tf!` accept them in place of a string key, which turns a misspelled key or a
+//! missing or unknown argument into a compile error; so do `user_error!` and `precondition_error!`,
+//! which only take them:
+//!
+//! ```ignore
+//! tl!(interaction, keys::errors::UNHANDLED, x = command_path.to_string())
+//! ```
+#![allow(non_camel_case_types)]
+
+pub trait LocaleKey {
+    const KEY: &'static str;
+}
+
+include!(concat!(env!("OUT_DIR"), "/keys.rs"));
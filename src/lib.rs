@@ -1,21 +1,48 @@
+extern crate self as hoshi;
+
 #[macro_use]
 pub mod macros;
 
 #[macro_use]
 extern crate rust_i18n;
 
-i18n!("locales", fallback = "en-US");
+// runtime locale files, when configured, take precedence over the bundled ones
+i18n!(
+    "locales",
+    fallback = "en-US",
+    backend = crate::runtime_locales::RuntimeBackend
+);
 
 use twilight_model::{guild::Role, user::User};
 
 pub mod localization;
 
+pub mod cli;
+pub mod collector;
 pub mod components;
+pub mod config;
 pub mod context;
+pub mod custom_id;
 pub mod error;
+pub mod gateway;
 pub mod interaction;
+pub mod keys;
+pub mod locale_check;
+pub mod logging;
+pub mod message_format;
+pub mod message_syntax;
+pub mod modal;
+pub mod options;
+pub mod preferences;
+pub mod registration;
+pub mod registry;
 pub mod resolver;
 pub mod response;
+pub mod runtime_locales;
+pub mod sessions;
+pub mod shutdown;
+pub mod storage;
+pub mod validation;
 
 pub mod commands;
 pub mod events;
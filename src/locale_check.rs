@@ -0,0 +1,270 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::Serialize;
+
+use crate::{
+    localization::LocaleMessages,
+    message_syntax::{message_arguments, parse_message},
+};
+
+/// the locale every other one is compared against; the fallback of `i18n!`
+pub const REFERENCE_LOCALE: &str = "en-US";
+
+/// how every locale compares to the reference locale
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct LocaleReport {
+    pub reference: String,
+    pub locales: Vec<LocaleCoverage>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct LocaleCoverage {
+    pub locale: String,
+    /// keys of the reference locale that this one doesn't have
+    pub missing: Vec<String>,
+    /// keys that only this locale has, which are never looked up
+    pub extra: Vec<String>,
+    pub placeholder_mismatches: Vec<PlaceholderMismatch>,
+    /// messages that don't parse, and so are shown without being formatted
+    pub invalid: Vec<InvalidMessage>,
+    /// the percentage of the keys of the reference locale that this one has
+    pub coverage: f64,
+}
+
+/// arguments that a message uses in one locale but not in the other
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct PlaceholderMismatch {
+    pub key: String,
+    /// used by the reference locale but not by this one
+    pub missing: Vec<String>,
+    /// used by this locale but not by the reference one
+    pub extra: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct InvalidMessage {
+    pub key: String,
+    pub error: String,
+}
+
+impl LocaleCoverage {
+    pub fn has_problems(&self) -> bool {
+        !self.missing.is_empty()
+            || !self.extra.is_empty()
+            || !self.placeholder_mismatches.is_empty()
+            || !self.invalid.is_empty()
+    }
+}
+
+impl LocaleReport {
+    pub fn has_problems(&self) -> bool {
+        self.locales.iter().any(LocaleCoverage::has_problems)
+    }
+}
+
+/// compares every locale, the reference one included so that its invalid messages show up, with
+/// the reference locale; `None` if there is no reference locale to compare with
+pub fn check_locales(locales: &LocaleMessages, reference: &str) -> Option<LocaleReport> {
+    let reference_messages = locales.get(reference)?;
+
+    Some(LocaleReport {
+        reference: reference.to_string(),
+        locales: locales
+            .iter()
+            .map(|(locale, messages)| check_locale(locale, messages, reference_messages))
+            .collect(),
+    })
+}
+
+fn check_locale(
+    locale: &str,
+    messages: &BTreeMap<String, String>,
+    reference_messages: &BTreeMap<String, String>,
+) -> LocaleCoverage {
+    let missing: Vec<String> = reference_messages
+        .keys()
+        .filter(|key| !messages.contains_key(*key))
+        .cloned()
+        .collect();
+
+    let extra = messages
+        .keys()
+        .filter(|key| !reference_messages.contains_key(*key))
+        .cloned()
+        .collect();
+
+    let mut placeholder_mismatches = Vec::new();
+    let mut invalid = Vec::new();
+
+    for (key, message) in messages {
+        let arguments = match parse_message(message) {
+            Ok(parts) => message_arguments(&parts),
+            Err(error) => {
+                invalid.push(InvalidMessage {
+                    key: key.clone(),
+                    error,
+                });
+                continue;
+            }
+        };
+
+        // an invalid reference message is reported for the reference locale itself
+        let Some(Ok(reference_parts)) = reference_messages
+            .get(key)
+            .map(|reference| parse_message(reference))
+        else {
+            continue;
+        };
+
+        let reference_arguments = message_arguments(&reference_parts);
+        let mismatch = PlaceholderMismatch {
+            key: key.clone(),
+            missing: difference(&reference_arguments, &arguments),
+            extra: difference(&arguments, &reference_arguments),
+        };
+
+        if !mismatch.missing.is_empty() || !mismatch.extra.is_empty() {
+            placeholder_mismatches.push(mismatch);
+        }
+    }
+
+    let coverage = if reference_messages.is_empty() {
+        100.0
+    } else {
+        (reference_messages.len() - missing.len()) as f64 / reference_messages.len() as f64 * 100.0
+    };
+
+    LocaleCoverage {
+        locale: locale.to_string(),
+        missing,
+        extra,
+        placeholder_mismatches,
+        invalid,
+        coverage,
+    }
+}
+
+fn difference(of: &[String], without: &[String]) -> Vec<String> {
+    of.iter()
+        .filter(|name| !without.contains(name))
+        .cloned()
+        .collect()
+}
+
+impl fmt::Display for LocaleReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for coverage in &self.locales {
+            writeln!(f, "{}: {:.1}% coverage", coverage.locale, coverage.coverage)?;
+
+            for key in &coverage.missing {
+                writeln!(f, "  missing: {key}")?;
+            }
+
+            for key in &coverage.extra {
+                writeln!(f, "  extra: {key}")?;
+            }
+
+            for mismatch in &coverage.placeholder_mismatches {
+                write!(f, "  placeholders: {}", mismatch.key)?;
+
+                if !mismatch.missing.is_empty() {
+                    write!(f, " (missing {})", mismatch.missing.join(", "))?;
+                }
+
+                if !mismatch.extra.is_empty() {
+                    write!(
+                        f,
+                        " (not in {}: {})",
+                        self.reference,
+                        mismatch.extra.join(", ")
+                    )?;
+                }
+
+                writeln!(f)?;
+            }
+
+            for message in &coverage.invalid {
+                writeln!(f, "  invalid: {}: {}", message.key, message.error)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::localization::read_locale_dir;
+
+    fn locale(messages: &[(&str, &str)]) -> BTreeMap<String, String> {
+        messages
+            .iter()
+            .map(|(key, message)| (key.to_string(), message.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn reports_missing_extra_and_mismatched_keys() {
+        let locales = LocaleMessages::from([
+            (
+                String::from("en-US"),
+                locale(&[
+                    ("greet", "hello <@%{user_id}>!"),
+                    ("items", "{count, plural, one {# item} other {# items}}"),
+                    ("ping", "pong"),
+                    ("bye", "bye"),
+                ]),
+            ),
+            (
+                String::from("ja"),
+                locale(&[
+                    ("greet", "こんにちは %{name}！"),
+                    ("items", "{count, plural, other {#個}}"),
+                    ("ping", "ポン"),
+                    ("unused", "{broken"),
+                ]),
+            ),
+        ]);
+
+        let report = check_locales(&locales, "en-US").unwrap();
+        let ja = report
+            .locales
+            .iter()
+            .find(|coverage| coverage.locale == "ja")
+            .unwrap();
+
+        assert_eq!(ja.missing, ["bye"]);
+        assert_eq!(ja.extra, ["unused"]);
+        assert_eq!(
+            ja.placeholder_mismatches,
+            [PlaceholderMismatch {
+                key: String::from("greet"),
+                missing: vec![String::from("user_id")],
+                extra: vec![String::from("name")],
+            }]
+        );
+        assert_eq!(ja.invalid.len(), 1);
+        assert_eq!(ja.invalid[0].key, "unused");
+        assert_eq!(ja.coverage, 75.0);
+
+        let en = report
+            .locales
+            .iter()
+            .find(|coverage| coverage.locale == "en-US")
+            .unwrap();
+
+        assert!(!en.has_problems());
+        assert_eq!(en.coverage, 100.0);
+        assert!(report.has_problems());
+        assert!(check_locales(&locales, "de").is_none());
+    }
+
+    #[test]
+    fn bundled_locales_are_complete() {
+        let locales = read_locale_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/locales")).unwrap();
+        let report = check_locales(&locales, REFERENCE_LOCALE).unwrap();
+
+        assert!(!report.has_problems(), "\n{report}");
+    }
+}
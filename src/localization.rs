@@ -1,6 +1,134 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    sync::{LazyLock, RwLock},
+};
 
-use twilight_model::application::command::{Command, CommandOption};
+use twilight_model::application::command::{Command, CommandOption, CommandOptionChoiceValue};
+
+use crate::{error::BotResult, runtime_locales};
+
+/// every locale discord sends and accepts localizations for
+pub const DISCORD_LOCALES: [&str; 32] = [
+    "id", "da", "de", "en-GB", "en-US", "es-ES", "es-419", "fr", "hr", "it", "lt", "hu", "nl",
+    "no", "pl", "pt-BR", "ro", "fi", "sv-SE", "vi", "tr", "cs", "el", "bg", "ru", "uk", "hi", "th",
+    "zh-CN", "ja", "zh-TW", "ko",
+];
+
+/// discord locale -> the locales to try, in order, when there is no file for it; set from the
+/// config on startup
+static LOCALE_FALLBACKS: LazyLock<RwLock<BTreeMap<String, Vec<String>>>> =
+    LazyLock::new(Default::default);
+
+pub fn set_locale_fallbacks(fallbacks: BTreeMap<String, Vec<String>>) {
+    *LOCALE_FALLBACKS
+        .write()
+        .expect("locale fallbacks lock poisoned") = fallbacks;
+}
+
+/// the available locale that fits a discord locale best: the locale itself, then its configured
+/// fallback chain, then another locale of the same language (`en-GB` -> `en-US`) and finally the
+/// default locale
+pub fn negotiate_locale(requested: Option<&str>) -> String {
+    let available = available_locales!();
+    let is_available = |locale: &str| available.contains(&locale);
+
+    let Some(requested) = requested else {
+        return rust_i18n::locale().to_string();
+    };
+
+    if is_available(requested) {
+        return requested.to_owned();
+    }
+
+    let fallbacks = LOCALE_FALLBACKS
+        .read()
+        .expect("locale fallbacks lock poisoned");
+
+    if let Some(fallback) = fallbacks
+        .get(requested)
+        .into_iter()
+        .flatten()
+        .find(|fallback| is_available(fallback))
+    {
+        return fallback.clone();
+    }
+
+    let language = language_of(requested);
+
+    available
+        .iter()
+        .find(|locale| language_of(locale) == language)
+        .map_or_else(
+            || rust_i18n::locale().to_string(),
+            |locale| locale.to_string(),
+        )
+}
+
+fn language_of(locale: &str) -> &str {
+    locale.split('-').next().unwrap_or(locale)
+}
+
+/// locale -> flattened key (`commands.roll.name`) -> message
+pub type LocaleMessages = BTreeMap<String, BTreeMap<String, String>>;
+
+/// every `.yml` file of the directory the way rust-i18n reads it, named after the file
+pub fn read_locale_dir(path: impl AsRef<Path>) -> BotResult<LocaleMessages> {
+    let mut locales = LocaleMessages::new();
+
+    for entry in std::fs::read_dir(path)? {
+        let path = entry?.path();
+
+        if !path
+            .extension()
+            .is_some_and(|extension| extension == "yml" || extension == "yaml")
+        {
+            continue;
+        }
+
+        let Some(locale) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        let value: serde_yaml::Value = serde_yaml::from_str(&std::fs::read_to_string(&path)?)?;
+        let mut messages = BTreeMap::new();
+
+        flatten_messages(&value, "", &mut messages);
+        locales.insert(locale.to_string(), messages);
+    }
+
+    Ok(locales)
+}
+
+fn flatten_messages(
+    value: &serde_yaml::Value,
+    prefix: &str,
+    messages: &mut BTreeMap<String, String>,
+) {
+    let Some(mapping) = value.as_mapping() else {
+        return;
+    };
+
+    for (segment, value) in mapping {
+        let Some(segment) = segment.as_str() else {
+            continue;
+        };
+
+        let key = if prefix.is_empty() {
+            segment.to_string()
+        } else {
+            format!("{prefix}.{segment}")
+        };
+
+        match value {
+            serde_yaml::Value::Mapping(_) => flatten_messages(value, &key, messages),
+            serde_yaml::Value::String(message) => {
+                messages.insert(key, message.clone());
+            }
+            _ => (),
+        }
+    }
+}
 
 pub fn available_localizations_of<Key: AsRef<str>>(key: Key) -> HashMap<String, String> {
     available_locales!()
@@ -9,49 +137,118 @@ pub fn available_localizations_of<Key: AsRef<str>>(key: Key) -> HashMap<String,
         .collect()
 }
 
+/// like `available_localizations_of`, but only the locales that have the key themselves, without
+/// falling back to another one, and nothing when no locale has it, so that a missing translation
+/// leaves the schema as it is instead of naming it after the key
+pub fn existing_localizations_of<Key: AsRef<str>>(key: Key) -> Option<HashMap<String, String>> {
+    let localizations: HashMap<String, String> = available_locales!()
+        .iter()
+        .filter_map(|locale| {
+            translation_of(locale, key.as_ref()).map(|value| (locale.to_string(), value))
+        })
+        .collect();
+
+    (!localizations.is_empty()).then_some(localizations)
+}
+
+/// the bundled locale files, read the same way as runtime ones
+static BUNDLED_LOCALES: LazyLock<LocaleMessages> = LazyLock::new(|| {
+    let files: &[(&str, &str)] = include!(concat!(env!("OUT_DIR"), "/bundled_locales.rs"));
+
+    files
+        .iter()
+        .map(|(locale, yaml)| {
+            let value: serde_yaml::Value =
+                serde_yaml::from_str(yaml).expect("bundled locale files are checked by build.rs");
+            let mut messages = BTreeMap::new();
+
+            flatten_messages(&value, "", &mut messages);
+            (locale.to_string(), messages)
+        })
+        .collect()
+});
+
+/// the message of the key in the locale itself, without falling back to another locale; the
+/// runtime locale files take precedence over the bundled ones, like they do for `t!`
+pub fn translation_of(locale: &str, key: &str) -> Option<String> {
+    runtime_locales::translate(locale, key)
+        .map(str::to_string)
+        .or_else(|| BUNDLED_LOCALES.get(locale)?.get(key).cloned())
+}
+
+fn extend_localizations(localizations: &mut Option<HashMap<String, String>>, key: String) {
+    if let Some(available_localizations) = existing_localizations_of(key) {
+        localizations
+            .get_or_insert_with(HashMap::new)
+            .extend(available_localizations);
+    }
+}
+
+/// how a choice is identified in localization keys
+pub fn choice_value_key(value: &CommandOptionChoiceValue) -> String {
+    match value {
+        CommandOptionChoiceValue::String(value) => value.clone(),
+        CommandOptionChoiceValue::Integer(value) => value.to_string(),
+        CommandOptionChoiceValue::Number(value) => value.to_string(),
+    }
+}
+
+/// localizes the option and everything nested in it: subcommands and their options under
+/// `<key>.options.<name>` and choices under `<key>.choices.<value>.name`
 pub fn localize_command_option(
     base_option_localization_key: String,
     command_option: &mut CommandOption,
 ) {
-    let available_name_localizations =
-        available_localizations_of(format!("{base_option_localization_key}.name"));
+    extend_localizations(
+        &mut command_option.name_localizations,
+        format!("{base_option_localization_key}.name"),
+    );
+    extend_localizations(
+        &mut command_option.description_localizations,
+        format!("{base_option_localization_key}.description"),
+    );
 
-    if let Some(name_localizations) = command_option.name_localizations.as_mut() {
-        name_localizations.extend(available_name_localizations);
-    } else {
-        command_option.name_localizations = Some(available_name_localizations);
-    }
-
-    let available_description_localizations =
-        available_localizations_of(format!("{base_option_localization_key}.description"));
+    command_option
+        .choices
+        .iter_mut()
+        .flatten()
+        .for_each(|choice| {
+            extend_localizations(
+                &mut choice.name_localizations,
+                format!(
+                    "{base_option_localization_key}.choices.{}.name",
+                    choice_value_key(&choice.value)
+                ),
+            )
+        });
 
-    if let Some(description_localizations) = command_option.description_localizations.as_mut() {
-        description_localizations.extend(available_description_localizations);
-    } else {
-        command_option.description_localizations = Some(available_description_localizations);
-    }
+    command_option
+        .options
+        .iter_mut()
+        .flatten()
+        .for_each(|nested_option| {
+            localize_command_option(
+                format!(
+                    "{base_option_localization_key}.options.{}",
+                    nested_option.name
+                ),
+                nested_option,
+            )
+        });
 }
 
-pub fn localize_command_schemas(command_schemas: &mut Vec<Command>) {
+pub fn localize_command_schemas(command_schemas: &mut [Command]) {
     command_schemas.iter_mut().for_each(|command| {
-        let available_name_localizations =
-            available_localizations_of(format!("commands.{}.name", command.name));
-
-        if let Some(name_localizations) = command.name_localizations.as_mut() {
-            name_localizations.extend(available_name_localizations);
-        } else {
-            command.name_localizations = Some(available_name_localizations);
-        }
+        extend_localizations(
+            &mut command.name_localizations,
+            format!("commands.{}.name", command.name),
+        );
 
         if !command.description.is_empty() {
-            let available_description_localizations =
-                available_localizations_of(format!("commands.{}.description", command.name));
-
-            if let Some(description_localizations) = command.description_localizations.as_mut() {
-                description_localizations.extend(available_description_localizations);
-            } else {
-                command.description_localizations = Some(available_description_localizations);
-            }
+            extend_localizations(
+                &mut command.description_localizations,
+                format!("commands.{}.description", command.name),
+            );
         }
 
         command.options.iter_mut().for_each(|command_option| {
@@ -62,3 +259,31 @@ pub fn localize_command_schemas(command_schemas: &mut Vec<Command>) {
         });
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiates_locales() {
+        set_locale_fallbacks(BTreeMap::from([
+            (
+                String::from("zh-TW"),
+                vec![String::from("de"), String::from("ja")],
+            ),
+            (String::from("ja"), vec![String::from("en-US")]),
+        ]));
+
+        let default_locale = rust_i18n::locale().to_string();
+
+        // an available locale is used as it is, even when it has fallbacks configured
+        assert_eq!(negotiate_locale(Some("ja")), "ja");
+        assert_eq!(negotiate_locale(Some("en-US")), "en-US");
+        // the first fallback that is available
+        assert_eq!(negotiate_locale(Some("zh-TW")), "ja");
+        // another locale of the same language
+        assert_eq!(negotiate_locale(Some("en-GB")), "en-US");
+        assert_eq!(negotiate_locale(Some("ko")), default_locale);
+        assert_eq!(negotiate_locale(None), default_locale);
+    }
+}
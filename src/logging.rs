@@ -0,0 +1,34 @@
+use clap::ValueEnum;
+use serde::Deserialize;
+use tracing_subscriber::{EnvFilter, fmt};
+
+use crate::error::BotResult;
+
+#[derive(Deserialize, ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Pretty,
+    /// one json object per line, for log collectors
+    Json,
+}
+
+/// `log_level` takes either a plain level (`info`) or full filter directives
+/// (`hoshi=debug,twilight_gateway=warn`); logs go to stderr so that stdout is left to the output
+/// of subcommands
+pub fn init(log_level: &str, log_format: LogFormat) -> BotResult<()> {
+    let filter = EnvFilter::try_new(log_level)?;
+    let subscriber = fmt().with_env_filter(filter).with_writer(std::io::stderr);
+
+    match log_format {
+        LogFormat::Pretty => subscriber.try_init(),
+        LogFormat::Json => subscriber
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .try_init(),
+    }
+    .map_err(|error| crate::error::BotError::internal(error.to_string()))?;
+
+    Ok(())
+}
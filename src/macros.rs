@@ -1,7 +1,73 @@
+#[macro_export]
+/// `t!` with the message formatted through `message_format`, so that it can use plurals, selects
+/// and locale-aware numbers next to `%{name}`; the locale has to be given
+///
+/// the key is either a string or one of `keys`, whose arguments are then checked at compile time
+macro_rules! tf {
+    (@format $key:expr, locale = $locale:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        // matched rather than bound so that a temporary locale lives for the whole expression
+        match $locale {
+            locale => $crate::message_format::format_message(
+                locale,
+                &t!($key, locale = locale),
+                &[$((stringify!($name), $crate::message_format::ToMessageArg::to_message_arg(&$value))),*],
+            ),
+        }
+    };
+    ($key:path, locale = $locale:expr $(, $name:ident = $value:expr)* $(,)?) => {{
+        // naming every field of the key's struct once is what makes a missing or unknown argument
+        // a compile error; the alias is needed as a struct literal can't take a path fragment
+        type Key = $key;
+        let _ = Key { $($name: ()),* };
+
+        tf!(
+            @format <$key as $crate::keys::LocaleKey>::KEY,
+            locale = $locale
+            $(, $name = $value)*
+        )
+    }};
+    ($key:expr, locale = $locale:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        tf!(@format $key, locale = $locale $(, $name = $value)*)
+    };
+}
+
 #[macro_export]
 /// expects that the first argument to the macro is a struct that contains a locale method which returns an owned string
 macro_rules! tl {
-    ($struct_with_locale:expr, $($all:tt)*) => {
-        t!($($all)*, locale = $struct_with_locale.locale().as_str())
+    ($struct_with_locale:expr, $key:path $(, $name:ident = $value:expr)* $(,)?) => {
+        tf!($key, locale = $struct_with_locale.locale().as_str() $(, $name = $value)*)
+    };
+    ($struct_with_locale:expr, $key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        tf!(@format $key, locale = $struct_with_locale.locale().as_str() $(, $name = $value)*)
+    };
+}
+
+#[macro_export]
+/// a `LocalizedMessage` for one of `keys`, with its arguments checked at compile time like `tf!`'s
+macro_rules! localized_message {
+    ($key:path $(, $name:ident = $value:expr)* $(,)?) => {{
+        type Key = $key;
+        let _ = Key { $($name: ()),* };
+
+        $crate::error::LocalizedMessage {
+            key: <$key as $crate::keys::LocaleKey>::KEY,
+            args: vec![$((stringify!($name), ::std::string::ToString::to_string(&$value))),*],
+        }
+    }};
+}
+
+#[macro_export]
+/// a `BotError::User` for one of `keys`: `user_error!(keys::errors::option::MISSING, name = name)`
+macro_rules! user_error {
+    ($($message:tt)*) => {
+        $crate::error::BotError::User($crate::localized_message!($($message)*))
+    };
+}
+
+#[macro_export]
+/// a `BotError::Precondition` for one of `keys`, like `user_error!`
+macro_rules! precondition_error {
+    ($($message:tt)*) => {
+        $crate::error::BotError::Precondition($crate::localized_message!($($message)*))
     };
 }
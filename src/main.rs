@@ -1,58 +1,290 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
+use clap::Parser;
 use hoshi::{
-    commands, context::BotContext, error::BotResult, events, localization::localize_command_schemas,
+    cli::{Cli, CliCommand, LocalesCommand, ReportFormat, SchemaCommand},
+    collector::ComponentCollectors,
+    commands, components,
+    config::Config,
+    context::BotContext,
+    custom_id::CustomIdCodec,
+    error::{BotError, BotResult},
+    events,
+    gateway::{GatewayIssue, GatewayStats},
+    locale_check::{self, REFERENCE_LOCALE},
+    localization, logging,
+    preferences::LocalePreferences,
+    registration::{self, CommandDiff, CommandSchemas, CommandScope},
+    runtime_locales,
+    sessions::SavedSessions,
+    shutdown::{self, Shutdown},
 };
-use rand::{SeedableRng, rngs::StdRng};
-use serde::Deserialize;
+use rand::{RngExt, SeedableRng, rngs::StdRng};
 use tokio::{sync::Mutex, task::JoinSet};
-use twilight_gateway::{ConfigBuilder, EventTypeFlags, Intents, Shard, StreamExt};
+use tracing::{Instrument, debug, error, info, info_span, warn};
+use twilight_gateway::{CloseFrame, ConfigBuilder, Event, EventTypeFlags, Shard, StreamExt};
 use twilight_http::Client as HttpClient;
 
-const INTENTS: Intents = Intents::empty();
 const EVENT_FLAGS: EventTypeFlags = EventTypeFlags::from_bits(
     EventTypeFlags::READY.bits() | EventTypeFlags::INTERACTION_CREATE.bits(),
 )
 .unwrap();
 
-async fn shard_handler(mut shard: Shard, context: Arc<BotContext>) {
-    while let Some(some_event) = shard.next_event(EVENT_FLAGS).await {
+/// how long in-flight interactions get to finish their responses once shutdown is triggered
+const SHUTDOWN_DEADLINE: Duration = Duration::from_secs(10);
+
+/// returns the shard once it has closed, so that its session can be saved
+async fn shard_handler(mut shard: Shard, context: Arc<BotContext>) -> Shard {
+    let mut is_closing = false;
+
+    loop {
+        let some_event = tokio::select! {
+            _ = context.shutdown.triggered(), if !is_closing => {
+                // resumable, so that the session can be picked up again after a restart
+                shard.close(CloseFrame::RESUME);
+                is_closing = true;
+                continue;
+            }
+            some_event = shard.next_event(EVENT_FLAGS) => some_event,
+        };
+
+        let Some(some_event) = some_event else {
+            break;
+        };
+
+        let shard_id = shard.id().number();
+
         let event = match some_event {
             Ok(event) => event,
-            Err(_) => continue,
+            Err(error) => {
+                let issue = GatewayIssue::from_error(&error);
+                let count = context.gateway_stats.record(issue);
+
+                warn!(
+                    shard_id,
+                    ?issue,
+                    count,
+                    ?error,
+                    "error receiving from the gateway"
+                );
+                continue;
+            }
+        };
+
+        if let Event::GatewayClose(frame) = &event {
+            // everything queued before the close frame has been received by now
+            if is_closing {
+                break;
+            }
+
+            if let Some(issue) = GatewayIssue::from_close(frame.as_ref()) {
+                context.gateway_stats.record(issue);
+
+                // the shard won't reconnect and the others would run into the same, so stop
+                error!(shard_id, ?issue, ?frame, "gateway closed the shard fatally");
+                context.shutdown.trigger();
+                context.collectors.close();
+                break;
+            }
+        }
+
+        let Some(event) = context.collectors.intercept(event) else {
+            continue;
         };
 
+        let span = info_span!("gateway_event", shard_id, kind = ?event.kind());
         let context = context.clone();
 
-        tokio::spawn(async move {
-            if let Err(err) = events::handle_event(event, context).await {
-                eprintln!("{err:?}");
+        context.shutdown.clone().spawn(
+            async move {
+                if let Err(error) = events::handle_event(event, context).await {
+                    error!(?error, "failed to handle event");
+                }
             }
-        });
+            .instrument(span),
+        );
     }
+
+    shard
 }
 
-async fn initialize_shards(shards: Vec<Shard>, context: Arc<BotContext>) {
+async fn initialize_shards(shards: Vec<Shard>, context: Arc<BotContext>) -> Vec<Shard> {
     let mut join_set = JoinSet::new();
 
     for shard in shards {
         join_set.spawn(shard_handler(shard, context.clone()));
     }
 
-    join_set.join_all().await;
-}
-
-#[derive(Deserialize, Debug)]
-struct EnvConfig {
-    token: String,
+    join_set.join_all().await
 }
 
 #[tokio::main]
 async fn main() -> BotResult<()> {
     let _ = dotenvy::dotenv();
-    let env_config = envy::from_env::<EnvConfig>()?;
+    let cli = Cli::parse();
+    let command = cli.command.unwrap_or(CliCommand::Run);
+
+    // logging isn't set up before the config is loaded, so its problems go straight to stderr
+    let config = match &command {
+        CliCommand::Schema(_) | CliCommand::Locales(_) => Config::load_without_token(&cli.config),
+        _ => Config::load(&cli.config),
+    };
+
+    let config = match config {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{error}");
+            std::process::exit(2);
+        }
+    };
+
+    logging::init(&config.log_level, config.log_format)?;
+    rust_i18n::set_locale(&config.default_locale);
+    localization::set_locale_fallbacks(config.locale_fallbacks.clone());
+
+    // before the schemas are validated, since their localizations come from these too
+    if let Some(locale_dir) = &config.locale_dir {
+        runtime_locales::load(locale_dir)?;
+        info!(dir = %locale_dir.display(), "loaded runtime locale files");
+    }
+
+    debug!(profile = %config.profile, ?config, "loaded config");
+
+    for warning in &config.warnings {
+        warn!("{warning}");
+    }
+
+    match command {
+        CliCommand::Run => {
+            validate_schemas(&config)?;
+            run(create_context(config).await?, cli.force).await
+        }
+        CliCommand::Schema(schema_command) => schema(schema_command, &config).await,
+        CliCommand::Locales(locales_command) => locales(locales_command),
+        CliCommand::Register { guild } => {
+            validate_schemas(&config)?;
+
+            let context = create_context(config).await?;
+
+            match guild {
+                Some(guild_id) => {
+                    registration::register_commands_to_guild(&context, guild_id, cli.force).await
+                }
+                None => registration::register_commands(&context, cli.force).await,
+            }
+        }
+        CliCommand::Unregister { guild } => {
+            let context = create_context(config).await?;
+
+            match guild {
+                Some(guild_id) => {
+                    registration::clear_commands(&context, CommandScope::Guild(guild_id)).await
+                }
+                None => {
+                    registration::clear_commands(&context, CommandScope::Global).await?;
+                    registration::clear_dev_guild_commands(&context).await
+                }
+            }
+        }
+    }
+}
+
+/// caught before registering rather than by a failed request to discord halfway through; left out
+/// of `schema` and `locales`, which are what a broken schema gets looked into with
+fn validate_schemas(config: &Config) -> BotResult<()> {
+    let schema_errors = CommandSchemas::new(
+        &commands::command_registry(&config.features),
+        &config.features,
+    )
+    .validate();
+
+    if schema_errors.is_empty() {
+        return Ok(());
+    }
+
+    for schema_error in &schema_errors {
+        error!(path = schema_error.path, "{}", schema_error.problem);
+    }
+
+    Err(BotError::internal(format!(
+        "{} problems in the command schemas",
+        schema_errors.len()
+    )))
+}
+
+async fn schema(schema_command: SchemaCommand, config: &Config) -> BotResult<()> {
+    let registry = commands::command_registry(&config.features);
+    let schemas = CommandSchemas::new(&registry, &config.features);
+    let select = |dev_guild: bool| {
+        if dev_guild {
+            schemas.dev_guild.clone()
+        } else {
+            schemas.global.clone()
+        }
+    };
+
+    match schema_command {
+        SchemaCommand::Dump { dev_guild } => {
+            println!("{}", registration::schemas_to_json(&select(dev_guild))?);
+        }
+        SchemaCommand::Diff {
+            snapshot,
+            dev_guild,
+        } => {
+            let snapshot = tokio::fs::read_to_string(&snapshot).await?;
+            let diff = CommandDiff::between(
+                &registration::schemas_from_json(&snapshot)?,
+                &select(dev_guild),
+            );
+
+            for command in &diff.added {
+                println!("+ {command}");
+            }
+
+            for command in &diff.changed {
+                println!("~ {command}");
+            }
+
+            for command in &diff.removed {
+                println!("- {command}");
+            }
+
+            if !diff.is_empty() {
+                std::process::exit(1);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn locales(locales_command: LocalesCommand) -> BotResult<()> {
+    match locales_command {
+        LocalesCommand::Check { dir, format } => {
+            let locales = localization::read_locale_dir(&dir)?;
+            let Some(report) = locale_check::check_locales(&locales, REFERENCE_LOCALE) else {
+                return Err(BotError::internal(format!(
+                    "there is no {REFERENCE_LOCALE} locale in {} to compare with",
+                    dir.display()
+                )));
+            };
 
-    let http_client = HttpClient::new(env_config.token.clone());
+            match format {
+                ReportFormat::Text => print!("{report}"),
+                ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+            }
+
+            if report.has_problems() {
+                std::process::exit(1);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn create_context(config: Config) -> BotResult<Arc<BotContext>> {
+    let http_client = HttpClient::new(config.token.clone());
 
     let user_application = http_client
         .current_user_application()
@@ -61,32 +293,140 @@ async fn main() -> BotResult<()> {
         .await?
         .clone();
 
-    let context = Arc::new(BotContext {
+    let custom_id_key = match config.custom_id_key.as_ref() {
+        Some(custom_id_key) => custom_id_key.as_bytes().to_vec(),
+        None => {
+            let mut custom_id_key = vec![0; 32];
+            rand::rng().fill(&mut custom_id_key[..]);
+            custom_id_key
+        }
+    };
+
+    let locale_preferences = LocalePreferences::load(&config.locale_preferences_file).await?;
+
+    Ok(Arc::new(BotContext {
+        commands: commands::command_registry(&config.features),
+        config,
         http_client,
         user_application,
         rng: Mutex::new(StdRng::from_rng(&mut rand::rng())),
+        components: components::component_registry(),
+        custom_ids: CustomIdCodec::new(&custom_id_key),
+        collectors: ComponentCollectors::new(),
+        shutdown: Shutdown::new(),
+        gateway_stats: GatewayStats::new(),
+        locale_preferences,
+    }))
+}
+
+async fn run(context: Arc<BotContext>, force: bool) -> BotResult<()> {
+    let config = &context.config;
+
+    if config.custom_id_key.is_none() {
+        warn!("custom_id_key is not set; using a random key for this run");
+    }
+
+    registration::register_commands(&context, force).await?;
+
+    if let Some(locale_dir) = config.locale_dir.clone() {
+        let watched_context = context.clone();
+
+        context.shutdown.spawn(async move {
+            if let Err(error) = runtime_locales::watch(locale_dir, watched_context).await {
+                error!(?error, "unable to watch locale files");
+            }
+        });
+    }
+
+    let saved_sessions = match SavedSessions::load(&config.session_file).await {
+        Ok(saved_sessions) => saved_sessions,
+        Err(error) => {
+            warn!(
+                ?error,
+                "unable to load saved sessions; identifying every shard"
+            );
+            SavedSessions::default()
+        }
+    };
+
+    let gateway_config = ConfigBuilder::new(config.token.clone(), config.intents).build();
+    let configure_shard =
+        |shard_id, builder: ConfigBuilder| saved_sessions.configure(shard_id, builder).build();
+
+    let shards = match config.shards {
+        Some(shards) => twilight_gateway::create_iterator(
+            shards.start..shards.end,
+            shards.total,
+            gateway_config,
+            configure_shard,
+        )
+        .collect::<Vec<_>>(),
+        None => twilight_gateway::create_recommended(
+            &context.http_client,
+            gateway_config,
+            configure_shard,
+        )
+        .await?
+        .collect::<Vec<_>>(),
+    };
+
+    info!(
+        shards = shards.len(),
+        resuming = shards
+            .iter()
+            .filter(|shard| shard.session().is_some())
+            .count(),
+        "spawning shards"
+    );
+
+    tokio::spawn({
+        let context = context.clone();
+
+        async move {
+            match shutdown::signal().await {
+                Ok(signal) => info!(signal, "shutting down"),
+                Err(error) => {
+                    // without signals there's nothing to shut down on, so keep running
+                    error!(?error, "unable to listen for shutdown signals");
+                    std::future::pending::<()>().await;
+                }
+            }
+
+            context.shutdown.trigger();
+            context.collectors.close();
+        }
     });
 
-    let mut command_schemas = commands::command_schemas();
+    let shards = initialize_shards(shards, context.clone()).await;
+    let sessions = SavedSessions::from_shards(&shards);
 
-    localize_command_schemas(&mut command_schemas);
+    match sessions.save(&config.session_file).await {
+        Ok(()) => info!(sessions = sessions.len(), "saved sessions"),
+        Err(error) => error!(?error, "unable to save sessions"),
+    }
 
-    context
-        .interaction()
-        .set_global_commands(&command_schemas)
-        .await?;
+    if !context.shutdown.wait_for_tasks(SHUTDOWN_DEADLINE).await {
+        warn!(
+            tasks = context.shutdown.running_tasks(),
+            "shutdown deadline passed with tasks still running"
+        );
+    }
 
-    let gateway_config = ConfigBuilder::new(env_config.token.clone(), INTENTS).build();
-    let shards = twilight_gateway::create_recommended(
-        &context.http_client,
-        gateway_config,
-        |_, builder: ConfigBuilder| builder.build(),
-    )
-    .await?;
+    if config.features.clear_guild_commands
+        && let Err(error) = registration::clear_dev_guild_commands(&context).await
+    {
+        error!(?error, "unable to clear the commands of the dev guilds");
+    }
+
+    let gateway_stats = context.gateway_stats.snapshot();
 
-    println!("spawning {} shards", shards.len());
+    info!(?gateway_stats, "shut down");
 
-    initialize_shards(shards.collect(), context).await;
+    if gateway_stats.fatal_closes > 0 {
+        return Err(BotError::internal(
+            "a shard was closed fatally by the gateway",
+        ));
+    }
 
     Ok(())
 }
@@ -0,0 +1,370 @@
+use std::{borrow::Cow, cell::RefCell, collections::HashMap, rc::Rc, str::FromStr};
+
+use icu_decimal::{DecimalFormatter, input::Decimal};
+use icu_locale_core::Locale;
+use icu_plurals::{PluralCategory, PluralRules};
+use tracing::warn;
+
+use crate::message_syntax::{Part, parse_message};
+
+/// a value interpolated into a message; numbers are formatted for the locale and can select a
+/// plural branch, text is inserted as it is (or parsed when it ends up in a number position)
+#[derive(Debug, Clone)]
+pub enum MessageArg {
+    Number(Decimal),
+    Text(String),
+}
+
+impl MessageArg {
+    fn number(&self) -> Option<Decimal> {
+        match self {
+            Self::Number(number) => Some(number.clone()),
+            Self::Text(text) => Decimal::from_str(text.trim()).ok(),
+        }
+    }
+
+    /// how it is inserted by `%{name}`, which predates locale-aware formatting and is used for
+    /// ids and the like that must not get grouping separators
+    fn plain(&self) -> String {
+        match self {
+            Self::Number(number) => number.to_string(),
+            Self::Text(text) => text.clone(),
+        }
+    }
+}
+
+/// what `tf!` and `tl!` accept as arguments; taken by reference so that interpolating a value
+/// doesn't move it
+pub trait ToMessageArg {
+    fn to_message_arg(&self) -> MessageArg;
+}
+
+macro_rules! impl_number_arg {
+    ($($number:ty),*) => {
+        $(
+            impl ToMessageArg for $number {
+                fn to_message_arg(&self) -> MessageArg {
+                    MessageArg::Number(Decimal::from(*self))
+                }
+            }
+        )*
+    };
+}
+
+impl_number_arg!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+impl ToMessageArg for f64 {
+    fn to_message_arg(&self) -> MessageArg {
+        match Decimal::from_str(&self.to_string()) {
+            Ok(number) => MessageArg::Number(number),
+            Err(_) => MessageArg::Text(self.to_string()),
+        }
+    }
+}
+
+impl ToMessageArg for f32 {
+    fn to_message_arg(&self) -> MessageArg {
+        f64::from(*self).to_message_arg()
+    }
+}
+
+impl ToMessageArg for str {
+    fn to_message_arg(&self) -> MessageArg {
+        MessageArg::Text(self.to_string())
+    }
+}
+
+impl ToMessageArg for String {
+    fn to_message_arg(&self) -> MessageArg {
+        MessageArg::Text(self.clone())
+    }
+}
+
+impl ToMessageArg for Cow<'_, str> {
+    fn to_message_arg(&self) -> MessageArg {
+        MessageArg::Text(self.to_string())
+    }
+}
+
+impl<T: ToMessageArg + ?Sized> ToMessageArg for &T {
+    fn to_message_arg(&self) -> MessageArg {
+        (**self).to_message_arg()
+    }
+}
+
+/// the subset of the ICU message format that locale strings can use, next to `%{name}`:
+///
+/// - `{name}` inserts the argument, formatting numbers for the locale
+/// - `{name, number}` formats the argument as a number
+/// - `{name, plural, =0 {none} one {# item} other {# items}}` picks a branch by the plural
+///   category of the number in the locale, with `#` being the formatted number
+/// - `{name, selectordinal, one {#st} two {#nd} few {#rd} other {#th}}` is the same for ordinals
+/// - `{name, select, a {...} other {...}}` picks a branch by the argument itself
+///
+/// `'` quotes braces and `#` (`'{'`), and `''` is a literal `'`
+pub fn format_message(locale: &str, message: &str, args: &[(&str, MessageArg)]) -> String {
+    // plain strings, which are the most of them, don't need to be parsed at all
+    if !message.contains(['{', '\'', '#']) {
+        return message.to_string();
+    }
+
+    // `build.rs` rejects these in the bundled locales, so they can only come from runtime ones
+    let parts = match parse_message(message) {
+        Ok(parts) => parts,
+        Err(error) => {
+            warn!(
+                message,
+                error, "unable to parse message; showing it as it is"
+            );
+            return message.to_string();
+        }
+    };
+
+    let formatters = formatters_for(locale);
+    let mut formatted = String::with_capacity(message.len());
+
+    format_parts(&parts, &formatters, args, None, &mut formatted);
+
+    formatted
+}
+
+struct Formatters {
+    cardinal: Option<PluralRules>,
+    ordinal: Option<PluralRules>,
+    decimal: Option<DecimalFormatter>,
+}
+
+impl Formatters {
+    fn new(locale: &str) -> Self {
+        let locale = Locale::try_from_str(locale).unwrap_or(Locale::UNKNOWN);
+
+        Self {
+            cardinal: PluralRules::try_new_cardinal((&locale).into()).ok(),
+            ordinal: PluralRules::try_new_ordinal((&locale).into()).ok(),
+            decimal: DecimalFormatter::try_new((&locale).into(), Default::default()).ok(),
+        }
+    }
+
+    fn number(&self, number: &Decimal) -> String {
+        match &self.decimal {
+            Some(decimal) => decimal.format(number).to_string(),
+            None => number.to_string(),
+        }
+    }
+
+    fn category(&self, number: &Decimal, ordinal: bool) -> PluralCategory {
+        let rules = if ordinal {
+            &self.ordinal
+        } else {
+            &self.cardinal
+        };
+
+        rules
+            .as_ref()
+            .map_or(PluralCategory::Other, |rules| rules.category_for(number))
+    }
+}
+
+thread_local! {
+    // the icu types can't be shared between threads, so every thread keeps its own
+    static FORMATTERS: RefCell<HashMap<String, Rc<Formatters>>> = RefCell::default();
+}
+
+fn formatters_for(locale: &str) -> Rc<Formatters> {
+    FORMATTERS.with_borrow_mut(|formatters| {
+        formatters
+            .entry(locale.to_string())
+            .or_insert_with(|| Rc::new(Formatters::new(locale)))
+            .clone()
+    })
+}
+
+fn format_parts(
+    parts: &[Part],
+    formatters: &Formatters,
+    args: &[(&str, MessageArg)],
+    pound: Option<&Decimal>,
+    formatted: &mut String,
+) {
+    let arg = |name: &str| {
+        args.iter()
+            .find(|(arg_name, _)| *arg_name == name)
+            .map(|(_, arg)| arg)
+    };
+
+    for part in parts {
+        match part {
+            Part::Text(text) => formatted.push_str(text),
+            // left as they are when missing, the same as rust-i18n does
+            Part::Legacy(name) => match arg(name) {
+                Some(arg) => formatted.push_str(&arg.plain()),
+                None => formatted.push_str(&format!("%{{{name}}}")),
+            },
+            Part::Argument(name) => match arg(name) {
+                Some(MessageArg::Number(number)) => formatted.push_str(&formatters.number(number)),
+                Some(MessageArg::Text(text)) => formatted.push_str(text),
+                None => formatted.push_str(&format!("{{{name}}}")),
+            },
+            Part::Number(name) => match arg(name) {
+                Some(arg) => match arg.number() {
+                    Some(number) => formatted.push_str(&formatters.number(&number)),
+                    None => formatted.push_str(&arg.plain()),
+                },
+                None => formatted.push_str(&format!("{{{name}}}")),
+            },
+            Part::Pound => match pound {
+                Some(number) => formatted.push_str(&formatters.number(number)),
+                None => formatted.push('#'),
+            },
+            Part::Plural {
+                name,
+                ordinal,
+                branches,
+            } => {
+                let number = arg(name).and_then(MessageArg::number);
+                let branch = number.as_ref().and_then(|number| {
+                    let category = formatters.category(number, *ordinal);
+
+                    find_branch(branches, |selector| match selector.strip_prefix('=') {
+                        Some(exact) => Decimal::from_str(exact).is_ok_and(|exact| exact == *number),
+                        None => false,
+                    })
+                    .or_else(|| {
+                        find_branch(branches, |selector| selector == category_name(category))
+                    })
+                });
+
+                if let Some(branch) = branch.or_else(|| find_branch(branches, |s| s == "other")) {
+                    format_parts(branch, formatters, args, number.as_ref(), formatted);
+                }
+            }
+            Part::Select { name, branches } => {
+                let value = arg(name).map(MessageArg::plain);
+                let branch = value
+                    .and_then(|value| find_branch(branches, |selector| selector == value))
+                    .or_else(|| find_branch(branches, |selector| selector == "other"));
+
+                if let Some(branch) = branch {
+                    format_parts(branch, formatters, args, pound, formatted);
+                }
+            }
+        }
+    }
+}
+
+fn find_branch(
+    branches: &[(String, Vec<Part>)],
+    matches: impl Fn(&str) -> bool,
+) -> Option<&[Part]> {
+    branches
+        .iter()
+        .find(|(selector, _)| matches(selector))
+        .map(|(_, parts)| parts.as_slice())
+}
+
+fn category_name(category: PluralCategory) -> &'static str {
+    match category {
+        PluralCategory::Zero => "zero",
+        PluralCategory::One => "one",
+        PluralCategory::Two => "two",
+        PluralCategory::Few => "few",
+        PluralCategory::Many => "many",
+        PluralCategory::Other => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(locale: &str, message: &str, args: &[(&str, &dyn ToMessageArg)]) -> String {
+        let args: Vec<_> = args
+            .iter()
+            .map(|(name, value)| (*name, value.to_message_arg()))
+            .collect();
+
+        format_message(locale, message, &args)
+    }
+
+    #[test]
+    fn formats_plurals() {
+        let message = "{count, plural, =0 {no items} one {# item} other {# items}}";
+
+        assert_eq!(format("en-US", message, &[("count", &0)]), "no items");
+        assert_eq!(format("en-US", message, &[("count", &1)]), "1 item");
+        assert_eq!(format("en-US", message, &[("count", &1234)]), "1,234 items");
+        assert_eq!(format("en-US", message, &[("count", &"1")]), "1 item");
+
+        let polish = "{count, plural, one {# plik} few {# pliki} many {# plików} other {# pliku}}";
+
+        assert_eq!(format("pl", polish, &[("count", &2)]), "2 pliki");
+        assert_eq!(format("pl", polish, &[("count", &5)]), "5 plików");
+        assert_eq!(format("pl", polish, &[("count", &1.5)]), "1,5 pliku");
+    }
+
+    #[test]
+    fn formats_ordinals() {
+        let message = "{place, selectordinal, one {#st} two {#nd} few {#rd} other {#th}}";
+        let places: Vec<_> = [1, 2, 3, 4, 11, 22]
+            .iter()
+            .map(|place| format("en-US", message, &[("place", place)]))
+            .collect();
+
+        assert_eq!(places, ["1st", "2nd", "3rd", "4th", "11th", "22nd"]);
+    }
+
+    #[test]
+    fn formats_selects() {
+        let message = "{kind, select, user {a user} role {a role} other {something}}";
+
+        assert_eq!(format("en-US", message, &[("kind", &"role")]), "a role");
+        assert_eq!(
+            format("en-US", message, &[("kind", &"channel")]),
+            "something"
+        );
+    }
+
+    #[test]
+    fn formats_numbers_for_the_locale_but_not_legacy_arguments() {
+        let message = "{n} {n, number} %{n}";
+
+        assert_eq!(
+            format("en-US", message, &[("n", &1234567)]),
+            "1,234,567 1,234,567 1234567"
+        );
+        assert_eq!(
+            format("de", message, &[("n", &1234567)]),
+            "1.234.567 1.234.567 1234567"
+        );
+        assert_eq!(format("en-US", "{n, number}", &[("n", &"many")]), "many");
+    }
+
+    #[test]
+    fn unquotes_apostrophes() {
+        assert_eq!(
+            format("en-US", "don't '{'n'}' {n}", &[("n", &5)]),
+            "don't {n} 5"
+        );
+        assert_eq!(format("en-US", "it''s", &[]), "it's");
+        assert_eq!(
+            format("en-US", "{n, plural, other {'#'#}}", &[("n", &3)]),
+            "#3"
+        );
+    }
+
+    #[test]
+    fn leaves_missing_arguments_in_place() {
+        assert_eq!(format("en-US", "{a} {b, number} %{c}", &[]), "{a} {b} %{c}");
+        assert_eq!(
+            format("en-US", "{n, plural, one {# item} other {# items}}", &[]),
+            "# items"
+        );
+    }
+
+    #[test]
+    fn shows_invalid_messages_as_they_are() {
+        assert_eq!(format("en-US", "{broken", &[("broken", &1)]), "{broken");
+    }
+}
@@ -0,0 +1,396 @@
+//! the syntax of locale strings, kept apart from `message_format` and free of dependencies so
+//! that `build.rs` can include it to read the arguments of every message
+
+/// one piece of a parsed message
+#[derive(Debug, Clone, PartialEq)]
+pub enum Part {
+    Text(String),
+    /// `%{name}`, the format of rust-i18n
+    Legacy(String),
+    /// `{name}`
+    Argument(String),
+    /// `{name, number}`
+    Number(String),
+    /// `#` inside of a plural branch, the number that selected it
+    Pound,
+    /// `{name, plural, ...}` and `{name, selectordinal, ...}`
+    Plural {
+        name: String,
+        ordinal: bool,
+        branches: Vec<(String, Vec<Part>)>,
+    },
+    /// `{name, select, ...}`
+    Select {
+        name: String,
+        branches: Vec<(String, Vec<Part>)>,
+    },
+}
+
+pub fn parse_message(message: &str) -> Result<Vec<Part>, String> {
+    let mut parser = Parser {
+        chars: message.chars().collect(),
+        position: 0,
+    };
+
+    let parts = parser.parts(false)?;
+
+    match parser.peek() {
+        None => Ok(parts),
+        Some(_) => Err(format!("unmatched `}}` at {}", parser.position)),
+    }
+}
+
+/// the names of every argument the message uses, `%{name}` included, in order of appearance
+pub fn message_arguments(parts: &[Part]) -> Vec<String> {
+    fn collect(parts: &[Part], names: &mut Vec<String>) {
+        for part in parts {
+            let (name, branches) = match part {
+                Part::Legacy(name) | Part::Argument(name) | Part::Number(name) => (name, None),
+                Part::Plural { name, branches, .. } | Part::Select { name, branches } => {
+                    (name, Some(branches))
+                }
+                Part::Text(_) | Part::Pound => continue,
+            };
+
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+
+            for (_, branch) in branches.into_iter().flatten() {
+                collect(branch, names);
+            }
+        }
+    }
+
+    let mut names = Vec::new();
+
+    collect(parts, &mut names);
+
+    names
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.position + offset).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some(found) if found == expected => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(found) => Err(format!(
+                "expected `{expected}` at {} but found `{found}`",
+                self.position
+            )),
+            None => Err(format!("expected `{expected}` but the message ended")),
+        }
+    }
+
+    /// up to the `}` that closes the surrounding branch, or the end of the message
+    fn parts(&mut self, in_plural: bool) -> Result<Vec<Part>, String> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+
+        while let Some(char) = self.peek() {
+            match char {
+                '}' => break,
+                '\'' => self.quoted(&mut text, in_plural),
+                '%' if self.peek_at(1) == Some('{') => {
+                    self.position += 2;
+                    let name = self.until('}')?;
+                    self.position += 1;
+
+                    push_text(&mut parts, &mut text);
+                    parts.push(Part::Legacy(name));
+                }
+                '{' => {
+                    self.position += 1;
+
+                    push_text(&mut parts, &mut text);
+                    parts.push(self.argument(in_plural)?);
+                }
+                '#' if in_plural => {
+                    self.position += 1;
+
+                    push_text(&mut parts, &mut text);
+                    parts.push(Part::Pound);
+                }
+                _ => {
+                    self.position += 1;
+                    text.push(char);
+                }
+            }
+        }
+
+        push_text(&mut parts, &mut text);
+
+        Ok(parts)
+    }
+
+    fn quoted(&mut self, text: &mut String, in_plural: bool) {
+        self.position += 1;
+
+        match self.peek() {
+            Some('\'') => {
+                self.position += 1;
+                text.push('\'');
+            }
+            Some('{' | '}') => self.quoted_until_end(text),
+            Some('#') if in_plural => self.quoted_until_end(text),
+            // an apostrophe that doesn't quote anything, as in "don't"
+            _ => text.push('\''),
+        }
+    }
+
+    fn quoted_until_end(&mut self, text: &mut String) {
+        while let Some(char) = self.peek() {
+            self.position += 1;
+
+            if char == '\'' {
+                if self.peek() == Some('\'') {
+                    self.position += 1;
+                    text.push('\'');
+                } else {
+                    return;
+                }
+            } else {
+                text.push(char);
+            }
+        }
+    }
+
+    /// the text up to `end`, which is left to be consumed
+    fn until(&mut self, end: char) -> Result<String, String> {
+        let start = self.position;
+
+        while let Some(char) = self.peek() {
+            if char == end {
+                return Ok(self.chars[start..self.position].iter().collect());
+            }
+
+            self.position += 1;
+        }
+
+        Err(format!("expected `{end}` but the message ended"))
+    }
+
+    /// a word made of anything but whitespace, braces and commas
+    fn word(&mut self) -> String {
+        self.skip_whitespace();
+
+        let start = self.position;
+
+        while self
+            .peek()
+            .is_some_and(|char| !char.is_whitespace() && !matches!(char, '{' | '}' | ','))
+        {
+            self.position += 1;
+        }
+
+        self.chars[start..self.position].iter().collect()
+    }
+
+    /// right after the opening `{`, up to and including the closing `}`
+    fn argument(&mut self, in_plural: bool) -> Result<Part, String> {
+        let name = self.word();
+
+        if name.is_empty() {
+            return Err(format!("expected an argument name at {}", self.position));
+        }
+
+        self.skip_whitespace();
+
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(Part::Argument(name));
+        }
+
+        self.expect(',')?;
+
+        let kind = self.word();
+
+        match kind.as_str() {
+            "number" => {
+                // styles such as `integer` aren't supported, but are allowed so that messages
+                // written for other ICU implementations still parse
+                self.until('}')?;
+                self.position += 1;
+
+                Ok(Part::Number(name))
+            }
+            "plural" | "selectordinal" => {
+                self.expect(',')?;
+
+                Ok(Part::Plural {
+                    name,
+                    ordinal: kind == "selectordinal",
+                    branches: self.branches(true)?,
+                })
+            }
+            "select" => {
+                self.expect(',')?;
+
+                Ok(Part::Select {
+                    name,
+                    branches: self.branches(in_plural)?,
+                })
+            }
+            _ => Err(format!("unknown argument type `{kind}` for `{name}`")),
+        }
+    }
+
+    /// `selector {message} ...` up to and including the closing `}` of the argument
+    fn branches(&mut self, in_plural: bool) -> Result<Vec<(String, Vec<Part>)>, String> {
+        let mut branches = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            if self.peek() == Some('}') {
+                self.position += 1;
+                break;
+            }
+
+            let selector = self.word();
+
+            if selector.is_empty() {
+                return Err(format!("expected a selector at {}", self.position));
+            }
+
+            self.expect('{')?;
+            let parts = self.parts(in_plural)?;
+            self.expect('}')?;
+
+            branches.push((selector, parts));
+        }
+
+        if !branches.iter().any(|(selector, _)| selector == "other") {
+            return Err(String::from(
+                "every plural and select needs an `other` branch",
+            ));
+        }
+
+        Ok(branches)
+    }
+}
+
+fn push_text(parts: &mut Vec<Part>, text: &mut String) {
+    if !text.is_empty() {
+        parts.push(Part::Text(std::mem::take(text)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str) -> Part {
+        Part::Text(text.to_string())
+    }
+
+    #[test]
+    fn parses_arguments() {
+        assert_eq!(
+            parse_message("hi %{name}, {count, number} of {total}").unwrap(),
+            [
+                text("hi "),
+                Part::Legacy(String::from("name")),
+                text(", "),
+                Part::Number(String::from("count")),
+                text(" of "),
+                Part::Argument(String::from("total")),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_plural_and_select_branches() {
+        assert_eq!(
+            parse_message("{count, plural, =0 {none} other {# left}}").unwrap(),
+            [Part::Plural {
+                name: String::from("count"),
+                ordinal: false,
+                branches: vec![
+                    (String::from("=0"), vec![text("none")]),
+                    (String::from("other"), vec![Part::Pound, text(" left")]),
+                ],
+            }]
+        );
+
+        // `#` is only special inside of a plural
+        assert_eq!(
+            parse_message("#{kind, select, a {#} other {b}}").unwrap(),
+            [
+                text("#"),
+                Part::Select {
+                    name: String::from("kind"),
+                    branches: vec![
+                        (String::from("a"), vec![text("#")]),
+                        (String::from("other"), vec![text("b")]),
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn apostrophes_quote_syntax() {
+        assert_eq!(parse_message("'{'braces'}'").unwrap(), [text("{braces}")]);
+        assert_eq!(parse_message("it''s").unwrap(), [text("it's")]);
+        assert_eq!(parse_message("don't").unwrap(), [text("don't")]);
+        assert_eq!(
+            parse_message("{n, plural, other {'#'# it''s}}").unwrap(),
+            [Part::Plural {
+                name: String::from("n"),
+                ordinal: false,
+                branches: vec![(
+                    String::from("other"),
+                    vec![text("#"), Part::Pound, text(" it's")],
+                )],
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_messages() {
+        for message in [
+            "{unclosed",
+            "unopened}",
+            "{}",
+            "{n, plural, one {#}}",
+            "{n, unknown}",
+            "{n, select, a {b} other {c}",
+        ] {
+            assert!(parse_message(message).is_err(), "{message:?}");
+        }
+    }
+
+    #[test]
+    fn lists_every_argument_once() {
+        let parts = parse_message(
+            "%{user} {count, plural, one {{user} and #} other {{others} and {user}}}",
+        )
+        .unwrap();
+
+        assert_eq!(message_arguments(&parts), ["user", "count", "others"]);
+    }
+}
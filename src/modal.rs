@@ -0,0 +1,122 @@
+use twilight_model::{
+    channel::message::{
+        Component,
+        component::{Label, TextInput, TextInputStyle},
+    },
+    http::interaction::InteractionResponseData,
+};
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::response::BotResponse;
+
+/// builds the data of a `InteractionResponseType::Modal` response; the custom_id is routed like
+/// the custom_id of a message component once the modal is submitted
+#[derive(Debug)]
+pub struct ModalBuilder {
+    custom_id: String,
+    title: String,
+    components: Vec<Component>,
+}
+
+impl ModalBuilder {
+    pub fn new(custom_id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            custom_id: custom_id.into(),
+            title: title.into(),
+            components: Vec::new(),
+        }
+    }
+
+    pub fn text_input(mut self, text_input: TextInputBuilder) -> Self {
+        self.components.push(text_input.build());
+        self
+    }
+
+    pub fn build(self) -> InteractionResponseData {
+        InteractionResponseDataBuilder::new()
+            .custom_id(self.custom_id)
+            .title(self.title)
+            .components(self.components)
+            .build()
+    }
+}
+
+impl From<ModalBuilder> for BotResponse {
+    fn from(value: ModalBuilder) -> Self {
+        BotResponse::Modal(value.build())
+    }
+}
+
+/// a text input wrapped in the label that discord requires around it inside of a modal
+#[derive(Debug)]
+pub struct TextInputBuilder {
+    label: String,
+    description: Option<String>,
+    text_input: TextInput,
+}
+
+impl TextInputBuilder {
+    pub fn new(
+        custom_id: impl Into<String>,
+        label: impl Into<String>,
+        style: TextInputStyle,
+    ) -> Self {
+        #[allow(deprecated)]
+        let text_input = TextInput {
+            id: None,
+            custom_id: custom_id.into(),
+            label: None,
+            max_length: None,
+            min_length: None,
+            placeholder: None,
+            required: None,
+            style,
+            value: None,
+        };
+
+        Self {
+            label: label.into(),
+            description: None,
+            text_input,
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.text_input.placeholder = Some(placeholder.into());
+        self
+    }
+
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.text_input.value = Some(value.into());
+        self
+    }
+
+    pub fn min_length(mut self, min_length: u16) -> Self {
+        self.text_input.min_length = Some(min_length);
+        self
+    }
+
+    pub fn max_length(mut self, max_length: u16) -> Self {
+        self.text_input.max_length = Some(max_length);
+        self
+    }
+
+    pub fn required(mut self, required: bool) -> Self {
+        self.text_input.required = Some(required);
+        self
+    }
+
+    pub fn build(self) -> Component {
+        Component::Label(Label {
+            id: None,
+            label: self.label,
+            description: self.description,
+            component: Box::new(Component::TextInput(self.text_input)),
+        })
+    }
+}
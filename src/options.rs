@@ -0,0 +1,312 @@
+use twilight_model::{
+    application::interaction::{
+        InteractionChannel, InteractionDataResolved, application_command::CommandOptionValue,
+    },
+    channel::Attachment,
+    guild::Role,
+    user::User,
+};
+use twilight_util::builder::command::{
+    AttachmentBuilder, BooleanBuilder, ChannelBuilder, IntegerBuilder, NumberBuilder, RoleBuilder,
+    StringBuilder, UserBuilder,
+};
+
+pub use hoshi_macros::CommandOptions;
+pub use twilight_model::application::command::CommandOption;
+
+use crate::{
+    error::{BotError, BotResult},
+    keys,
+    resolver::ApplicationCommandInteractionChatInputDataResolver,
+};
+
+/// the options of a chat input command as a struct; derived with `#[derive(CommandOptions)]` so
+/// that the schema's options and the parsing of them come from the same definition
+pub trait CommandOptions: Sized {
+    fn options() -> Vec<CommandOption>;
+
+    fn from_resolver(
+        resolver: &ApplicationCommandInteractionChatInputDataResolver,
+    ) -> BotResult<Self>;
+}
+
+/// a type that can be the value of a chat input command option
+pub trait OptionValue: Sized {
+    fn option(name: &str, description: &str) -> CommandOption;
+
+    fn from_value(
+        value: &CommandOptionValue,
+        resolved: Option<&InteractionDataResolved>,
+    ) -> Option<Self>;
+}
+
+impl OptionValue for String {
+    fn option(name: &str, description: &str) -> CommandOption {
+        StringBuilder::new(name, description).build()
+    }
+
+    fn from_value(value: &CommandOptionValue, _: Option<&InteractionDataResolved>) -> Option<Self> {
+        let CommandOptionValue::String(value) = value else {
+            return None;
+        };
+
+        Some(value.clone())
+    }
+}
+
+impl OptionValue for i64 {
+    fn option(name: &str, description: &str) -> CommandOption {
+        IntegerBuilder::new(name, description).build()
+    }
+
+    fn from_value(value: &CommandOptionValue, _: Option<&InteractionDataResolved>) -> Option<Self> {
+        let CommandOptionValue::Integer(value) = value else {
+            return None;
+        };
+
+        Some(*value)
+    }
+}
+
+impl OptionValue for f64 {
+    fn option(name: &str, description: &str) -> CommandOption {
+        NumberBuilder::new(name, description).build()
+    }
+
+    fn from_value(value: &CommandOptionValue, _: Option<&InteractionDataResolved>) -> Option<Self> {
+        let CommandOptionValue::Number(value) = value else {
+            return None;
+        };
+
+        Some(*value)
+    }
+}
+
+impl OptionValue for bool {
+    fn option(name: &str, description: &str) -> CommandOption {
+        BooleanBuilder::new(name, description).build()
+    }
+
+    fn from_value(value: &CommandOptionValue, _: Option<&InteractionDataResolved>) -> Option<Self> {
+        let CommandOptionValue::Boolean(value) = value else {
+            return None;
+        };
+
+        Some(*value)
+    }
+}
+
+impl OptionValue for User {
+    fn option(name: &str, description: &str) -> CommandOption {
+        UserBuilder::new(name, description).build()
+    }
+
+    fn from_value(
+        value: &CommandOptionValue,
+        resolved: Option<&InteractionDataResolved>,
+    ) -> Option<Self> {
+        let CommandOptionValue::User(value) = value else {
+            return None;
+        };
+
+        resolved?.users.get(value).cloned()
+    }
+}
+
+impl OptionValue for InteractionChannel {
+    fn option(name: &str, description: &str) -> CommandOption {
+        ChannelBuilder::new(name, description).build()
+    }
+
+    fn from_value(
+        value: &CommandOptionValue,
+        resolved: Option<&InteractionDataResolved>,
+    ) -> Option<Self> {
+        let CommandOptionValue::Channel(value) = value else {
+            return None;
+        };
+
+        resolved?.channels.get(value).cloned()
+    }
+}
+
+impl OptionValue for Role {
+    fn option(name: &str, description: &str) -> CommandOption {
+        RoleBuilder::new(name, description).build()
+    }
+
+    fn from_value(
+        value: &CommandOptionValue,
+        resolved: Option<&InteractionDataResolved>,
+    ) -> Option<Self> {
+        let CommandOptionValue::Role(value) = value else {
+            return None;
+        };
+
+        resolved?.roles.get(value).cloned()
+    }
+}
+
+impl OptionValue for Attachment {
+    fn option(name: &str, description: &str) -> CommandOption {
+        AttachmentBuilder::new(name, description).build()
+    }
+
+    fn from_value(
+        value: &CommandOptionValue,
+        resolved: Option<&InteractionDataResolved>,
+    ) -> Option<Self> {
+        let CommandOptionValue::Attachment(value) = value else {
+            return None;
+        };
+
+        resolved?.attachments.get(value).cloned()
+    }
+}
+
+/// used by the derive; `Ok(None)` when the option wasn't given and an error when it was given
+/// with a value of a different type than the field
+pub fn extract<T: OptionValue>(
+    resolver: &ApplicationCommandInteractionChatInputDataResolver,
+    option_name: &str,
+) -> BotResult<Option<T>> {
+    let Some(value) = resolver.get_option_value(option_name) else {
+        return Ok(None);
+    };
+
+    match T::from_value(value, resolver.resolved()) {
+        Some(value) => Ok(Some(value)),
+        None => Err(user_error!(
+            keys::errors::option::INVALID_TYPE,
+            name = option_name
+        )),
+    }
+}
+
+/// used by the derive for required fields
+pub fn missing_option_error(option_name: &str) -> BotError {
+    user_error!(keys::errors::option::MISSING, name = option_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::application::{
+        command::CommandOptionType, interaction::application_command::CommandDataOption,
+    };
+
+    use super::*;
+
+    #[derive(CommandOptions, Debug)]
+    struct TestOptions {
+        #[option(description = "some text")]
+        text: String,
+        #[option(description = "a flag")]
+        flag: bool,
+        #[option(name = "amount", description = "how many")]
+        count: Option<i64>,
+        #[option(description = "a ratio", autocomplete)]
+        ratio: Option<f64>,
+    }
+
+    fn resolver(
+        options: Vec<(&str, CommandOptionValue)>,
+    ) -> ApplicationCommandInteractionChatInputDataResolver {
+        ApplicationCommandInteractionChatInputDataResolver::new(
+            String::from("test"),
+            None,
+            None,
+            options
+                .into_iter()
+                .map(|(name, value)| CommandDataOption {
+                    name: name.to_string(),
+                    value,
+                })
+                .collect(),
+            None,
+        )
+    }
+
+    fn user_error(error: BotError) -> (String, Vec<(&'static str, String)>) {
+        match error {
+            BotError::User(message) => (message.key.to_owned(), message.args),
+            error => panic!("expected a user error, got {error}"),
+        }
+    }
+
+    #[test]
+    fn options_follow_the_fields() {
+        let options = TestOptions::options();
+        let summary: Vec<_> = options
+            .iter()
+            .map(|option| {
+                (
+                    option.name.as_str(),
+                    option.kind,
+                    option.required,
+                    option.autocomplete == Some(true),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                ("text", CommandOptionType::String, Some(true), false),
+                ("flag", CommandOptionType::Boolean, Some(true), false),
+                ("amount", CommandOptionType::Integer, Some(false), false),
+                ("ratio", CommandOptionType::Number, Some(false), true),
+            ]
+        );
+        assert_eq!(options[0].description, "some text");
+    }
+
+    #[test]
+    fn parses_required_and_optional_values() {
+        let options = TestOptions::from_resolver(&resolver(vec![
+            ("text", CommandOptionValue::String(String::from("hi"))),
+            ("amount", CommandOptionValue::Integer(-3)),
+            ("flag", CommandOptionValue::Boolean(true)),
+        ]))
+        .unwrap();
+
+        assert_eq!(options.text, "hi");
+        assert_eq!(options.count, Some(-3));
+        assert_eq!(options.ratio, None);
+        assert!(options.flag);
+    }
+
+    #[test]
+    fn missing_required_option_is_a_user_error() {
+        let error = TestOptions::from_resolver(&resolver(vec![(
+            "text",
+            CommandOptionValue::String(String::from("hi")),
+        )]))
+        .unwrap_err();
+
+        assert_eq!(
+            user_error(error),
+            (
+                String::from("errors.option.missing"),
+                vec![("name", String::from("flag"))]
+            )
+        );
+    }
+
+    #[test]
+    fn wrong_type_is_a_user_error() {
+        let error = TestOptions::from_resolver(&resolver(vec![
+            ("text", CommandOptionValue::String(String::from("hi"))),
+            ("amount", CommandOptionValue::String(String::from("three"))),
+            ("flag", CommandOptionValue::Boolean(false)),
+        ]))
+        .unwrap_err();
+
+        assert_eq!(
+            user_error(error),
+            (
+                String::from("errors.option.invalid_type"),
+                vec![("name", String::from("amount"))]
+            )
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use std::{
+    collections::BTreeMap,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use twilight_model::id::{
+    Id,
+    marker::{GuildMarker, UserMarker},
+};
+
+use crate::{error::BotResult, storage};
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+struct StoredPreferences {
+    users: BTreeMap<Id<UserMarker>, String>,
+    guilds: BTreeMap<Id<GuildMarker>, String>,
+}
+
+/// the locales users and guilds picked with `/language`, which take precedence over the ones
+/// discord sends along with an interaction; written to disk on every change
+#[derive(Debug)]
+pub struct LocalePreferences {
+    path: PathBuf,
+    stored: RwLock<StoredPreferences>,
+    /// held while writing so that an older snapshot can't overwrite a newer one
+    save_lock: Mutex<()>,
+}
+
+impl LocalePreferences {
+    /// a missing file is the same as nobody having picked a locale yet
+    pub async fn load(path: impl AsRef<Path>) -> BotResult<Self> {
+        let path = path.as_ref();
+
+        let stored = match tokio::fs::read(path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(error) if error.kind() == ErrorKind::NotFound => StoredPreferences::default(),
+            Err(error) => return Err(error.into()),
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            stored: RwLock::new(stored),
+            save_lock: Mutex::new(()),
+        })
+    }
+
+    pub fn user(&self, user_id: Id<UserMarker>) -> Option<String> {
+        self.read().users.get(&user_id).cloned()
+    }
+
+    pub fn guild(&self, guild_id: Id<GuildMarker>) -> Option<String> {
+        self.read().guilds.get(&guild_id).cloned()
+    }
+
+    /// `None` goes back to the locale discord sends
+    pub async fn set_user(&self, user_id: Id<UserMarker>, locale: Option<String>) -> BotResult<()> {
+        {
+            let mut stored = self.write();
+
+            match locale {
+                Some(locale) => stored.users.insert(user_id, locale),
+                None => stored.users.remove(&user_id),
+            };
+        }
+
+        self.save().await
+    }
+
+    /// `None` goes back to the locale discord sends
+    pub async fn set_guild(
+        &self,
+        guild_id: Id<GuildMarker>,
+        locale: Option<String>,
+    ) -> BotResult<()> {
+        {
+            let mut stored = self.write();
+
+            match locale {
+                Some(locale) => stored.guilds.insert(guild_id, locale),
+                None => stored.guilds.remove(&guild_id),
+            };
+        }
+
+        self.save().await
+    }
+
+    async fn save(&self) -> BotResult<()> {
+        let _save_guard = self.save_lock.lock().await;
+        let bytes = serde_json::to_vec_pretty(&*self.read())?;
+
+        storage::write_atomically(&self.path, bytes).await
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, StoredPreferences> {
+        self.stored
+            .read()
+            .expect("locale preferences lock poisoned")
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, StoredPreferences> {
+        self.stored
+            .write()
+            .expect("locale preferences lock poisoned")
+    }
+}
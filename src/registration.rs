@@ -0,0 +1,394 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde_json::Value;
+use tracing::{info, warn};
+use twilight_model::{
+    application::command::Command,
+    id::{Id, marker::GuildMarker},
+};
+
+use crate::{
+    config::Features,
+    context::BotContext,
+    error::BotResult,
+    localization::localize_command_schemas,
+    registry::CommandRegistry,
+    validation::{SchemaError, validate_command_schemas},
+};
+
+/// the schemas of the registry, localized, split by where they get registered
+#[derive(Debug, Clone, Default)]
+pub struct CommandSchemas {
+    pub global: Vec<Command>,
+    /// the same for every dev guild
+    pub dev_guild: Vec<Command>,
+}
+
+impl CommandSchemas {
+    /// with the `guild_commands` feature every command moves from global to the dev guilds
+    pub fn new(registry: &CommandRegistry, features: &Features) -> Self {
+        let mut global = registry.schemas();
+        let mut dev_guild = registry.dev_guild_schemas();
+
+        if features.guild_commands {
+            dev_guild.splice(0..0, global.drain(..));
+        }
+
+        localize_command_schemas(&mut global);
+        localize_command_schemas(&mut dev_guild);
+
+        Self { global, dev_guild }
+    }
+
+    pub fn of(context: &BotContext) -> Self {
+        Self::new(&context.commands, &context.config.features)
+    }
+
+    /// every command, for registering to a single guild
+    pub fn all(&self) -> Vec<Command> {
+        self.global.iter().chain(&self.dev_guild).cloned().collect()
+    }
+
+    /// a dev guild ends up with the global commands as well as its own, so both are checked
+    /// together for duplicates and limits
+    pub fn validate(&self) -> Vec<SchemaError> {
+        validate_command_schemas(&self.all())
+    }
+}
+
+/// the commands as they are sent to discord, but with the keys sorted so that the output stays
+/// the same between runs and can be diffed
+pub fn schemas_to_json(schemas: &[Command]) -> BotResult<String> {
+    Ok(serde_json::to_string_pretty(&serde_json::to_value(
+        schemas,
+    )?)?)
+}
+
+/// reads commands written by `schemas_to_json`
+pub fn schemas_from_json(json: &str) -> BotResult<Vec<Command>> {
+    Ok(serde_json::from_str(json)?)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandScope {
+    Global,
+    Guild(Id<GuildMarker>),
+}
+
+impl fmt::Display for CommandScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Global => f.write_str("global"),
+            Self::Guild(guild_id) => write!(f, "guild {guild_id}"),
+        }
+    }
+}
+
+/// names of the commands that differ between what is registered and what should be, matched by
+/// name and kind
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandDiff {
+    pub added: Vec<String>,
+    pub changed: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl CommandDiff {
+    pub fn between(registered: &[Command], schemas: &[Command]) -> Self {
+        let registered = keyed(registered);
+        let schemas = keyed(schemas);
+        let mut diff = Self::default();
+
+        for (key, schema) in &schemas {
+            match registered.get(key) {
+                None => diff.added.push(key.1.clone()),
+                Some(command) if normalized(command) != normalized(schema) => {
+                    diff.changed.push(key.1.clone())
+                }
+                Some(_) => (),
+            }
+        }
+
+        diff.removed = registered
+            .keys()
+            .filter(|key| !schemas.contains_key(*key))
+            .map(|key| key.1.clone())
+            .collect();
+
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+fn keyed(commands: &[Command]) -> BTreeMap<(u8, String), &Command> {
+    commands
+        .iter()
+        .map(|command| ((u8::from(command.kind), command.name.clone()), command))
+        .collect()
+}
+
+/// the command as json without what discord assigns on registration, and without the
+/// difference between a field being left out and it being empty or `false`, which discord
+/// doesn't keep apart when it sends the commands back
+fn normalized(command: &Command) -> Value {
+    let mut value = serde_json::to_value(command).unwrap_or_default();
+
+    if let Value::Object(object) = &mut value {
+        for key in [
+            "id",
+            "application_id",
+            "version",
+            "guild_id",
+            "dm_permission",
+        ] {
+            object.remove(key);
+        }
+    }
+
+    strip_defaults(&mut value);
+
+    value
+}
+
+fn strip_defaults(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            object.values_mut().for_each(strip_defaults);
+            object.retain(|_, value| !is_default(value));
+        }
+        Value::Array(array) => array.iter_mut().for_each(strip_defaults),
+        _ => (),
+    }
+}
+
+fn is_default(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(false) => true,
+        Value::Array(array) => array.is_empty(),
+        Value::Object(object) => object.is_empty(),
+        _ => false,
+    }
+}
+
+pub async fn registered_commands(
+    context: &BotContext,
+    scope: CommandScope,
+) -> BotResult<Vec<Command>> {
+    let interaction_client = context.interaction();
+
+    Ok(match scope {
+        CommandScope::Global => {
+            interaction_client
+                .global_commands()
+                .with_localizations(true)
+                .await?
+                .models()
+                .await?
+        }
+        CommandScope::Guild(guild_id) => {
+            interaction_client
+                .guild_commands(guild_id)
+                .with_localizations(true)
+                .await?
+                .models()
+                .await?
+        }
+    })
+}
+
+/// overwrites the commands of the scope, but only when they differ from the schemas unless
+/// `force` is set
+pub async fn sync_commands(
+    context: &BotContext,
+    scope: CommandScope,
+    schemas: &[Command],
+    force: bool,
+) -> BotResult<CommandDiff> {
+    let registered = registered_commands(context, scope).await?;
+    let diff = CommandDiff::between(&registered, schemas);
+
+    for command in &diff.added {
+        info!(%scope, command, "command added");
+    }
+
+    for command in &diff.changed {
+        info!(%scope, command, "command changed");
+    }
+
+    for command in &diff.removed {
+        info!(%scope, command, "command removed");
+    }
+
+    if diff.is_empty() && !force {
+        info!(%scope, "commands are up to date");
+
+        return Ok(diff);
+    }
+
+    let interaction_client = context.interaction();
+
+    match scope {
+        CommandScope::Global => {
+            interaction_client.set_global_commands(schemas).await?;
+        }
+        CommandScope::Guild(guild_id) => {
+            interaction_client
+                .set_guild_commands(guild_id, schemas)
+                .await?;
+        }
+    }
+
+    info!(%scope, commands = schemas.len(), "commands overwritten");
+
+    Ok(diff)
+}
+
+/// syncs the schemas globally and to every dev guild; the global commands are left alone when
+/// everything goes to the dev guilds, so a dev instance doesn't replace what prod registered
+pub async fn register_commands(context: &BotContext, force: bool) -> BotResult<()> {
+    let schemas = CommandSchemas::of(context);
+
+    if !context.config.features.guild_commands {
+        sync_commands(context, CommandScope::Global, &schemas.global, force).await?;
+    }
+
+    if context.config.dev_guild_ids.is_empty() && !schemas.dev_guild.is_empty() {
+        warn!(
+            commands = schemas.dev_guild.len(),
+            "no dev guilds are configured; dev guild commands are not registered anywhere"
+        );
+    }
+
+    for guild_id in &context.config.dev_guild_ids {
+        sync_commands(
+            context,
+            CommandScope::Guild(*guild_id),
+            &schemas.dev_guild,
+            force,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// syncs every command, dev guild ones included, to a single guild
+pub async fn register_commands_to_guild(
+    context: &BotContext,
+    guild_id: Id<GuildMarker>,
+    force: bool,
+) -> BotResult<()> {
+    let schemas = CommandSchemas::of(context).all();
+
+    sync_commands(context, CommandScope::Guild(guild_id), &schemas, force).await?;
+
+    Ok(())
+}
+
+pub async fn clear_commands(context: &BotContext, scope: CommandScope) -> BotResult<()> {
+    let interaction_client = context.interaction();
+
+    match scope {
+        CommandScope::Global => {
+            interaction_client.set_global_commands(&[]).await?;
+        }
+        CommandScope::Guild(guild_id) => {
+            interaction_client.set_guild_commands(guild_id, &[]).await?;
+        }
+    }
+
+    info!(%scope, "commands cleared");
+
+    Ok(())
+}
+
+/// removes every command from the dev guilds; used on exit with the `clear_guild_commands`
+/// feature so that a stopped dev instance doesn't leave dead commands behind
+pub async fn clear_dev_guild_commands(context: &BotContext) -> BotResult<()> {
+    for guild_id in &context.config.dev_guild_ids {
+        clear_commands(context, CommandScope::Guild(*guild_id)).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::application::command::CommandType;
+    use twilight_util::builder::command::{CommandBuilder, StringBuilder};
+
+    use super::*;
+
+    fn command(name: &str, description: &str) -> Command {
+        CommandBuilder::new(name, description, CommandType::ChatInput)
+            .option(StringBuilder::new("text", "some text"))
+            .build()
+    }
+
+    #[test]
+    fn identical_schemas_are_up_to_date() {
+        let schemas = [command("ping", "pong!"), command("roll", "roll")];
+
+        assert!(CommandDiff::between(&schemas, &schemas).is_empty());
+    }
+
+    #[test]
+    fn ignores_what_discord_assigns() {
+        let schema = command("ping", "pong!");
+        let mut registered = schema.clone();
+        registered.id = Some(Id::new(1));
+        registered.application_id = Some(Id::new(2));
+        registered.guild_id = Some(Id::new(3));
+        registered.version = Id::new(4);
+
+        assert!(CommandDiff::between(&[registered], &[schema]).is_empty());
+    }
+
+    #[test]
+    fn ignores_fields_left_at_their_defaults() {
+        let schema = command("ping", "pong!");
+        let mut registered = schema.clone();
+        registered.nsfw = Some(false);
+        registered.description_localizations = Some(Default::default());
+        registered.options[0].autocomplete = Some(false);
+        registered.options[0].choices = Some(Vec::new());
+
+        let mut defaulted = schema.clone();
+        defaulted.nsfw = None;
+        defaulted.options[0].autocomplete = None;
+        defaulted.options[0].required = None;
+
+        assert!(CommandDiff::between(&[registered], std::slice::from_ref(&schema)).is_empty());
+        assert!(CommandDiff::between(&[defaulted], &[schema]).is_empty());
+    }
+
+    #[test]
+    fn detects_added_changed_and_removed_commands() {
+        let registered = [command("ping", "pong!"), command("old", "going away")];
+        let schemas = [command("ping", "pong?"), command("new", "just added")];
+
+        assert_eq!(
+            CommandDiff::between(&registered, &schemas),
+            CommandDiff {
+                added: vec![String::from("new")],
+                changed: vec![String::from("ping")],
+                removed: vec![String::from("old")],
+            }
+        );
+    }
+
+    #[test]
+    fn tells_commands_of_different_kinds_apart() {
+        let registered = [command("greet", "")];
+        let mut schema = command("greet", "");
+        schema.kind = CommandType::User;
+
+        let diff = CommandDiff::between(&registered, &[schema]);
+
+        assert_eq!(diff.added, ["greet"]);
+        assert_eq!(diff.removed, ["greet"]);
+    }
+}
@@ -0,0 +1,350 @@
+use std::{collections::HashMap, fmt, future::Future, pin::Pin, sync::Arc};
+
+use twilight_model::application::command::{
+    Command, CommandOptionChoice, CommandOptionType, CommandType,
+};
+
+use crate::{
+    context::BotContext,
+    error::BotResult,
+    interaction::ApplicationCommandInteraction,
+    keys,
+    resolver::{
+        ApplicationCommandInteractionChatInputDataResolver,
+        ApplicationCommandInteractionMessageDataResolver,
+        ApplicationCommandInteractionUserDataResolver, CUSTOM_ID_SEPARATOR,
+        InteractionDataResolver, MessageComponentInteractionDataResolver,
+        ModalSubmitInteractionDataResolver,
+    },
+    response::BotResponse,
+};
+
+pub type CommandFuture<'a> =
+    Pin<Box<dyn Future<Output = BotResult<Option<BotResponse>>> + Send + 'a>>;
+
+pub type AutocompleteFuture<'a> =
+    Pin<Box<dyn Future<Output = BotResult<Vec<CommandOptionChoice>>> + Send + 'a>>;
+
+type CommandHandlers<R> = HashMap<CommandPath, Arc<dyn BotCommand<Resolver = R>>>;
+
+/// a command is its schema and the handler for every path of that schema; which kind of
+/// command it is (chat input, message or user) is decided by its resolver
+pub trait BotCommand: Send + Sync + 'static {
+    type Resolver: CommandKind;
+
+    fn schema(&self) -> Command;
+
+    fn command<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<'_, '_, Self::Resolver>,
+        context: Arc<BotContext>,
+    ) -> CommandFuture<'a>;
+
+    /// suggestions for the focused option of an autocomplete interaction; only called for
+    /// options that have `autocomplete` set in the schema
+    fn autocomplete<'a>(
+        &'a self,
+        _interaction: &'a mut ApplicationCommandInteraction<'_, '_, Self::Resolver>,
+        _context: Arc<BotContext>,
+    ) -> AutocompleteFuture<'a> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+}
+
+/// implemented by the resolvers of application commands to tie them to a `CommandType` and
+/// to their set of handlers inside of the registry
+pub trait CommandKind: InteractionDataResolver + Sized + 'static {
+    const KIND: CommandType;
+
+    fn handlers(registry: &CommandRegistry) -> &CommandHandlers<Self>;
+
+    fn handlers_mut(registry: &mut CommandRegistry) -> &mut CommandHandlers<Self>;
+}
+
+impl CommandKind for ApplicationCommandInteractionChatInputDataResolver {
+    const KIND: CommandType = CommandType::ChatInput;
+
+    fn handlers(registry: &CommandRegistry) -> &CommandHandlers<Self> {
+        &registry.chat_input
+    }
+
+    fn handlers_mut(registry: &mut CommandRegistry) -> &mut CommandHandlers<Self> {
+        &mut registry.chat_input
+    }
+}
+
+impl CommandKind for ApplicationCommandInteractionMessageDataResolver {
+    const KIND: CommandType = CommandType::Message;
+
+    fn handlers(registry: &CommandRegistry) -> &CommandHandlers<Self> {
+        &registry.message
+    }
+
+    fn handlers_mut(registry: &mut CommandRegistry) -> &mut CommandHandlers<Self> {
+        &mut registry.message
+    }
+}
+
+impl CommandKind for ApplicationCommandInteractionUserDataResolver {
+    const KIND: CommandType = CommandType::User;
+
+    fn handlers(registry: &CommandRegistry) -> &CommandHandlers<Self> {
+        &registry.user
+    }
+
+    fn handlers_mut(registry: &mut CommandRegistry) -> &mut CommandHandlers<Self> {
+        &mut registry.user
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CommandPath {
+    pub command: String,
+    pub subcommand_group: Option<String>,
+    pub subcommand: Option<String>,
+}
+
+impl CommandPath {
+    pub fn new(
+        command: impl Into<String>,
+        subcommand_group: Option<String>,
+        subcommand: Option<String>,
+    ) -> Self {
+        Self {
+            command: command.into(),
+            subcommand_group,
+            subcommand,
+        }
+    }
+
+    /// every path that can be invoked from the schema, one per (nested) subcommand or just the
+    /// command itself if it has none
+    fn leaves_of(schema: &Command) -> Vec<Self> {
+        let mut paths = Vec::new();
+
+        for option in &schema.options {
+            match option.kind {
+                CommandOptionType::SubCommand => paths.push(Self::new(
+                    schema.name.clone(),
+                    None,
+                    Some(option.name.clone()),
+                )),
+                CommandOptionType::SubCommandGroup => {
+                    paths.extend(option.options.iter().flatten().map(|subcommand| {
+                        Self::new(
+                            schema.name.clone(),
+                            Some(option.name.clone()),
+                            Some(subcommand.name.clone()),
+                        )
+                    }))
+                }
+                _ => (),
+            }
+        }
+
+        if paths.is_empty() {
+            paths.push(Self::new(schema.name.clone(), None, None));
+        }
+
+        paths
+    }
+}
+
+impl fmt::Display for CommandPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.command)?;
+
+        if let Some(subcommand_group) = &self.subcommand_group {
+            write!(f, " {subcommand_group}")?;
+        }
+
+        if let Some(subcommand) = &self.subcommand {
+            write!(f, " {subcommand}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// built once at startup; owns the schemas that get registered to discord and the handlers
+/// that those schemas dispatch to, so one can't exist without the other
+#[derive(Default)]
+pub struct CommandRegistry {
+    schemas: Vec<Command>,
+    /// never registered globally, only to the dev guilds
+    dev_guild_schemas: Vec<Command>,
+    chat_input: CommandHandlers<ApplicationCommandInteractionChatInputDataResolver>,
+    message: CommandHandlers<ApplicationCommandInteractionMessageDataResolver>,
+    user: CommandHandlers<ApplicationCommandInteractionUserDataResolver>,
+}
+
+impl CommandRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// panics if the schema's kind doesn't match the command's resolver or if any of its paths
+    /// are already registered, both of which are programming errors caught on startup
+    pub fn register<C: BotCommand>(mut self, command: C) -> Self {
+        let schema = self.insert_handlers(command);
+
+        self.schemas.push(schema);
+
+        self
+    }
+
+    /// like `register`, but the command only ever gets registered to the dev guilds
+    pub fn register_in_dev_guilds<C: BotCommand>(mut self, command: C) -> Self {
+        let schema = self.insert_handlers(command);
+
+        self.dev_guild_schemas.push(schema);
+
+        self
+    }
+
+    fn insert_handlers<C: BotCommand>(&mut self, command: C) -> Command {
+        let schema = command.schema();
+
+        assert_eq!(
+            schema.kind,
+            C::Resolver::KIND,
+            "schema of command \"{}\" does not match the kind of its resolver",
+            schema.name
+        );
+
+        let command: Arc<dyn BotCommand<Resolver = C::Resolver>> = Arc::new(command);
+        let handlers = C::Resolver::handlers_mut(self);
+
+        for path in CommandPath::leaves_of(&schema) {
+            assert!(
+                !handlers.contains_key(&path),
+                "command \"{path}\" is registered more than once"
+            );
+
+            handlers.insert(path, command.clone());
+        }
+
+        schema
+    }
+
+    /// the schemas that are registered globally
+    pub fn schemas(&self) -> Vec<Command> {
+        self.schemas.clone()
+    }
+
+    pub fn dev_guild_schemas(&self) -> Vec<Command> {
+        self.dev_guild_schemas.clone()
+    }
+
+    pub fn get<R: CommandKind>(
+        &self,
+        path: &CommandPath,
+    ) -> Option<Arc<dyn BotCommand<Resolver = R>>> {
+        R::handlers(self).get(path).cloned()
+    }
+}
+
+impl fmt::Debug for CommandRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandRegistry")
+            .field(
+                "commands",
+                &self
+                    .schemas
+                    .iter()
+                    .map(|schema| schema.name.as_str())
+                    .collect::<Vec<_>>(),
+            )
+            .field(
+                "dev_guild_commands",
+                &self
+                    .dev_guild_schemas
+                    .iter()
+                    .map(|schema| schema.name.as_str())
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// handles every message component and submitted modal whose custom_id starts with its namespace
+pub trait BotComponent: Send + Sync + 'static {
+    fn namespace(&self) -> &'static str;
+
+    fn component<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<
+            '_,
+            '_,
+            MessageComponentInteractionDataResolver,
+        >,
+        _context: Arc<BotContext>,
+    ) -> CommandFuture<'a> {
+        Box::pin(async move {
+            Ok(Some(BotResponse::Error(tl!(
+                interaction,
+                keys::errors::UNHANDLED,
+                x = interaction.data_resolver.custom_id
+            ))))
+        })
+    }
+
+    fn modal<'a>(
+        &'a self,
+        interaction: &'a mut ApplicationCommandInteraction<
+            '_,
+            '_,
+            ModalSubmitInteractionDataResolver,
+        >,
+        _context: Arc<BotContext>,
+    ) -> CommandFuture<'a> {
+        Box::pin(async move {
+            Ok(Some(BotResponse::Error(tl!(
+                interaction,
+                keys::errors::UNHANDLED,
+                x = interaction.data_resolver.custom_id
+            ))))
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct ComponentRegistry {
+    components: HashMap<&'static str, Arc<dyn BotComponent>>,
+}
+
+impl ComponentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// panics if the namespace is already taken, which is a programming error caught on startup
+    pub fn register<C: BotComponent>(mut self, component: C) -> Self {
+        let namespace = component.namespace();
+
+        assert!(
+            !namespace.contains(CUSTOM_ID_SEPARATOR),
+            "component namespace \"{namespace}\" contains the custom_id separator"
+        );
+        assert!(
+            !self.components.contains_key(namespace),
+            "component namespace \"{namespace}\" is registered more than once"
+        );
+
+        self.components.insert(namespace, Arc::new(component));
+
+        self
+    }
+
+    pub fn get(&self, namespace: &str) -> Option<Arc<dyn BotComponent>> {
+        self.components.get(namespace).cloned()
+    }
+}
+
+impl fmt::Debug for ComponentRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComponentRegistry")
+            .field("namespaces", &self.components.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -1,9 +1,13 @@
+use std::collections::HashMap;
+
 use twilight_model::{
     application::interaction::{
         InteractionChannel, InteractionDataResolved,
         application_command::{CommandData, CommandDataOption, CommandOptionValue},
+        message_component::MessageComponentInteractionData,
+        modal::{ModalInteractionComponent, ModalInteractionData},
     },
-    channel::{Attachment, Message},
+    channel::{Attachment, Message, message::component::ComponentType},
     guild::Role,
     user::User,
 };
@@ -83,6 +87,30 @@ impl ApplicationCommandInteractionChatInputDataResolver {
             .find(|option| option.name == option_name)
     }
 
+    pub fn get_option_value<OptionName: AsRef<str>>(
+        &self,
+        option_name: OptionName,
+    ) -> Option<&CommandOptionValue> {
+        Some(&self.get_option(option_name)?.value)
+    }
+
+    /// the name and current (partial) value of the option the user is typing in during an
+    /// autocomplete interaction
+    pub fn get_focused_option(&self) -> Option<(&str, &str)> {
+        self.command_options
+            .iter()
+            .find_map(|option| match &option.value {
+                CommandOptionValue::Focused(value, _) => {
+                    Some((option.name.as_str(), value.as_str()))
+                }
+                _ => None,
+            })
+    }
+
+    pub fn resolved(&self) -> Option<&InteractionDataResolved> {
+        self.command_resolved.as_ref()
+    }
+
     pub fn get_string_option<OptionName: AsRef<str>>(
         &self,
         option_name: OptionName,
@@ -172,10 +200,8 @@ impl ApplicationCommandInteractionChatInputDataResolver {
 
         if let Some(user) = resolved.users.get(&value.cast()) {
             Some(Mentionable::User(user))
-        } else if let Some(role) = resolved.roles.get(&value.cast()) {
-            Some(Mentionable::Role(role))
         } else {
-            None
+            resolved.roles.get(&value.cast()).map(Mentionable::Role)
         }
     }
 
@@ -220,3 +246,164 @@ impl ApplicationCommandInteractionUserDataResolver {
 }
 
 impl InteractionDataResolver for ApplicationCommandInteractionUserDataResolver {}
+
+/// separates the namespace of a custom_id, which decides the component handler, from the rest
+/// of it, which is left for that handler to interpret
+pub const CUSTOM_ID_SEPARATOR: char = ':';
+
+fn split_custom_id(custom_id: &str) -> (&str, &str) {
+    custom_id
+        .split_once(CUSTOM_ID_SEPARATOR)
+        .unwrap_or((custom_id, ""))
+}
+
+#[derive(Debug)]
+pub struct MessageComponentInteractionDataResolver {
+    pub custom_id: String,
+    pub component_type: ComponentType,
+    pub values: Vec<String>,
+    component_resolved: Option<InteractionDataResolved>,
+}
+
+impl MessageComponentInteractionDataResolver {
+    pub fn new(
+        custom_id: String,
+        component_type: ComponentType,
+        values: Vec<String>,
+        resolved: Option<InteractionDataResolved>,
+    ) -> Self {
+        Self {
+            custom_id,
+            component_type,
+            values,
+            component_resolved: resolved,
+        }
+    }
+
+    pub fn from_component_data(component_data: MessageComponentInteractionData) -> Self {
+        Self {
+            custom_id: component_data.custom_id,
+            component_type: component_data.component_type,
+            values: component_data.values,
+            component_resolved: component_data.resolved,
+        }
+    }
+
+    pub fn namespace(&self) -> &str {
+        split_custom_id(&self.custom_id).0
+    }
+
+    /// everything in the custom_id after the namespace
+    pub fn arguments(&self) -> &str {
+        split_custom_id(&self.custom_id).1
+    }
+
+    pub fn get_selected_users(&self) -> Vec<&User> {
+        let Some(resolved) = self.component_resolved.as_ref() else {
+            return Vec::new();
+        };
+
+        self.values
+            .iter()
+            .filter_map(|value| resolved.users.get(&value.parse().ok()?))
+            .collect()
+    }
+
+    pub fn get_selected_roles(&self) -> Vec<&Role> {
+        let Some(resolved) = self.component_resolved.as_ref() else {
+            return Vec::new();
+        };
+
+        self.values
+            .iter()
+            .filter_map(|value| resolved.roles.get(&value.parse().ok()?))
+            .collect()
+    }
+
+    pub fn get_selected_channels(&self) -> Vec<&InteractionChannel> {
+        let Some(resolved) = self.component_resolved.as_ref() else {
+            return Vec::new();
+        };
+
+        self.values
+            .iter()
+            .filter_map(|value| resolved.channels.get(&value.parse().ok()?))
+            .collect()
+    }
+
+    pub fn get_selected_mentionables(&self) -> Vec<Mentionable<'_>> {
+        let Some(resolved) = self.component_resolved.as_ref() else {
+            return Vec::new();
+        };
+
+        self.values
+            .iter()
+            .filter_map(|value| {
+                let id = value.parse().ok()?;
+
+                if let Some(user) = resolved.users.get(&id) {
+                    Some(Mentionable::User(user))
+                } else {
+                    resolved.roles.get(&id.cast()).map(Mentionable::Role)
+                }
+            })
+            .collect()
+    }
+}
+
+impl InteractionDataResolver for MessageComponentInteractionDataResolver {}
+
+#[derive(Debug)]
+pub struct ModalSubmitInteractionDataResolver {
+    pub custom_id: String,
+    text_inputs: HashMap<String, String>,
+    modal_resolved: Option<InteractionDataResolved>,
+}
+
+impl ModalSubmitInteractionDataResolver {
+    pub fn from_modal_data(modal_data: ModalInteractionData) -> Self {
+        let mut text_inputs = HashMap::new();
+        let mut pending_components = modal_data.components;
+
+        while let Some(component) = pending_components.pop() {
+            match component {
+                ModalInteractionComponent::Label(label) => {
+                    pending_components.push(*label.component)
+                }
+                ModalInteractionComponent::ActionRow(action_row) => {
+                    pending_components.extend(action_row.components)
+                }
+                ModalInteractionComponent::TextInput(text_input) => {
+                    text_inputs.insert(text_input.custom_id, text_input.value);
+                }
+                _ => (),
+            }
+        }
+
+        Self {
+            custom_id: modal_data.custom_id,
+            text_inputs,
+            modal_resolved: modal_data.resolved,
+        }
+    }
+
+    pub fn namespace(&self) -> &str {
+        split_custom_id(&self.custom_id).0
+    }
+
+    /// everything in the custom_id after the namespace
+    pub fn arguments(&self) -> &str {
+        split_custom_id(&self.custom_id).1
+    }
+
+    /// the value of a text input by its custom_id; empty when an optional input was left empty
+    pub fn get_text_input<CustomId: AsRef<str>>(&self, custom_id: CustomId) -> Option<&str> {
+        self.text_inputs.get(custom_id.as_ref()).map(String::as_str)
+    }
+
+    pub fn resolved(&self) -> Option<&InteractionDataResolved> {
+        self.modal_resolved.as_ref()
+    }
+}
+
+impl InteractionDataResolver for ModalSubmitInteractionDataResolver {}
@@ -1,12 +1,29 @@
-use twilight_model::http::interaction::InteractionResponseData;
+use twilight_model::{
+    channel::message::AllowedMentions, http::interaction::InteractionResponseData,
+};
+use twilight_util::builder::InteractionResponseDataBuilder;
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
 pub enum BotResponse {
     ResponseData(InteractionResponseData),
+    /// answers the interaction with a modal; built with `modal::ModalBuilder`
+    Modal(InteractionResponseData),
+    /// plain text that doesn't ping anyone, since it can carry what a user typed; mentions that
+    /// should ping go through `ResponseData` with their own `allowed_mentions`
     Content(String),
     Error(String),
 }
 
+impl BotResponse {
+    pub fn content_data(content: String) -> InteractionResponseData {
+        InteractionResponseDataBuilder::new()
+            .content(content)
+            .allowed_mentions(AllowedMentions::default())
+            .build()
+    }
+}
+
 impl<T: Into<String>> From<T> for BotResponse {
     fn from(value: T) -> Self {
         BotResponse::Content(value.into())
@@ -0,0 +1,211 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock, Mutex, RwLock},
+    time::Duration,
+};
+
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+use crate::{
+    context::BotContext,
+    error::BotResult,
+    locale_check::{self, REFERENCE_LOCALE},
+    localization,
+    registration::{self, CommandDiff, CommandSchemas},
+};
+
+/// editors tend to write a file in several steps, so changes are only picked up once the
+/// directory has been quiet for this long
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// locale -> flattened key -> message
+pub type Translations = HashMap<&'static str, HashMap<String, &'static str>>;
+
+/// swapped as a whole on every reload, so a lookup never sees half of the old files and half of
+/// the new ones
+static TRANSLATIONS: LazyLock<RwLock<Arc<Translations>>> = LazyLock::new(Default::default);
+
+/// rust-i18n hands out borrowed strings, so every message has to live for the rest of the
+/// process; interning them means a reload only leaks the messages that actually changed
+static INTERNED: LazyLock<Mutex<HashSet<&'static str>>> = LazyLock::new(Default::default);
+
+fn intern(string: &str) -> &'static str {
+    let mut interned = INTERNED.lock().expect("interned strings lock poisoned");
+
+    if let Some(string) = interned.get(string) {
+        return string;
+    }
+
+    let string: &'static str = Box::leak(string.into());
+    interned.insert(string);
+
+    string
+}
+
+fn current() -> Arc<Translations> {
+    TRANSLATIONS
+        .read()
+        .expect("runtime translations lock poisoned")
+        .clone()
+}
+
+fn replace(translations: Arc<Translations>) -> Arc<Translations> {
+    std::mem::replace(
+        &mut *TRANSLATIONS
+            .write()
+            .expect("runtime translations lock poisoned"),
+        translations,
+    )
+}
+
+/// the translations read from `locale_dir`; empty until `load` is called, which leaves the
+/// bundled ones in charge
+pub struct RuntimeBackend;
+
+impl rust_i18n::Backend for RuntimeBackend {
+    fn available_locales(&self) -> Vec<&str> {
+        current().keys().copied().collect()
+    }
+
+    fn translate(&self, locale: &str, key: &str) -> Option<&str> {
+        translate(locale, key)
+    }
+}
+
+/// the message of the key in the runtime locale files, without falling back to another locale
+pub fn translate(locale: &str, key: &str) -> Option<&'static str> {
+    current().get(locale)?.get(key).copied()
+}
+
+/// reads the locale files of the directory and swaps them in for the previous ones; returns the
+/// previous ones so that a reload can be undone
+pub fn load(dir: impl AsRef<Path>) -> BotResult<Arc<Translations>> {
+    let locales = localization::read_locale_dir(dir)?;
+
+    // not fatal, since these still show up as the raw message, but worth knowing about after an
+    // edit
+    if let Some(report) = locale_check::check_locales(&locales, REFERENCE_LOCALE) {
+        for coverage in &report.locales {
+            for message in &coverage.invalid {
+                warn!(
+                    locale = coverage.locale,
+                    key = message.key,
+                    error = message.error,
+                    "invalid message in the runtime locale files"
+                );
+            }
+        }
+    }
+
+    let translations = locales
+        .iter()
+        .map(|(locale, messages)| {
+            (
+                intern(locale),
+                messages
+                    .iter()
+                    .map(|(key, message)| (key.clone(), intern(message)))
+                    .collect(),
+            )
+        })
+        .collect();
+
+    Ok(replace(Arc::new(translations)))
+}
+
+fn is_locale_change(event: &Event) -> bool {
+    !matches!(event.kind, EventKind::Access(_))
+        && event.paths.iter().any(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "yml" || extension == "yaml")
+        })
+}
+
+/// reloads the locale files whenever they change until shutdown, and syncs the commands when
+/// that changed their localized names or descriptions
+pub async fn watch(dir: PathBuf, context: Arc<BotContext>) -> BotResult<()> {
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event| {
+        let _ = sender.send(event);
+    })?;
+
+    watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+    info!(dir = %dir.display(), "watching locale files");
+
+    let mut schemas = CommandSchemas::of(&context);
+
+    loop {
+        let event = tokio::select! {
+            _ = context.shutdown.triggered() => break,
+            event = receiver.recv() => event,
+        };
+
+        match event {
+            Some(Ok(event)) if is_locale_change(&event) => (),
+            Some(Ok(_)) => continue,
+            Some(Err(error)) => {
+                warn!(?error, "error watching locale files");
+                continue;
+            }
+            None => break,
+        }
+
+        loop {
+            tokio::select! {
+                _ = context.shutdown.triggered() => return Ok(()),
+                _ = tokio::time::sleep(DEBOUNCE) => break,
+                Some(_) = receiver.recv() => (),
+            }
+        }
+
+        let previous = match load(&dir) {
+            Ok(previous) => previous,
+            Err(error) => {
+                warn!(
+                    ?error,
+                    "unable to reload locale files; keeping the previous ones"
+                );
+                continue;
+            }
+        };
+
+        info!("reloaded locale files");
+
+        let reloaded = CommandSchemas::of(&context);
+
+        if CommandDiff::between(&schemas.global, &reloaded.global).is_empty()
+            && CommandDiff::between(&schemas.dev_guild, &reloaded.dev_guild).is_empty()
+        {
+            continue;
+        }
+
+        // discord would reject them anyway, and the bot would refuse to start with them
+        let schema_errors = reloaded.validate();
+
+        if !schema_errors.is_empty() {
+            for schema_error in &schema_errors {
+                error!(path = schema_error.path, "{}", schema_error.problem);
+            }
+
+            replace(previous);
+            error!(
+                problems = schema_errors.len(),
+                "reloaded locale files break the command schemas; keeping the previous ones"
+            );
+            continue;
+        }
+
+        match registration::register_commands(&context, false).await {
+            Ok(()) => schemas = reloaded,
+            Err(error) => error!(
+                ?error,
+                "unable to sync commands after reloading locale files"
+            ),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use std::{collections::BTreeMap, io::ErrorKind, path::Path};
+
+use serde::{Deserialize, Serialize};
+use twilight_gateway::{ConfigBuilder, Session, Shard, ShardId};
+
+use crate::{error::BotResult, storage};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SavedSession {
+    pub session: Session,
+    pub resume_url: Option<String>,
+}
+
+/// the gateway sessions of the shards as they were when the bot last shut down, so that a
+/// restart can resume them instead of identifying again
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SavedSessions {
+    /// sessions only resume on the same shard of the same total
+    shard_total: u32,
+    shards: BTreeMap<u32, SavedSession>,
+}
+
+impl SavedSessions {
+    /// a missing file is the same as no saved sessions
+    pub async fn load(path: impl AsRef<Path>) -> BotResult<Self> {
+        match tokio::fs::read(path).await {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub async fn save(&self, path: impl AsRef<Path>) -> BotResult<()> {
+        storage::write_atomically(path, serde_json::to_vec_pretty(self)?).await
+    }
+
+    pub fn from_shards<'a>(shards: impl IntoIterator<Item = &'a Shard>) -> Self {
+        let mut sessions = Self::default();
+
+        for shard in shards {
+            sessions.shard_total = shard.id().total();
+
+            if let Some(session) = shard.session() {
+                sessions.shards.insert(
+                    shard.id().number(),
+                    SavedSession {
+                        session: session.clone(),
+                        resume_url: shard.resume_url().map(String::from),
+                    },
+                );
+            }
+        }
+
+        sessions
+    }
+
+    pub fn get(&self, shard_id: ShardId) -> Option<&SavedSession> {
+        if shard_id.total() != self.shard_total {
+            return None;
+        }
+
+        self.shards.get(&shard_id.number())
+    }
+
+    pub fn len(&self) -> usize {
+        self.shards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shards.is_empty()
+    }
+
+    /// the shard resumes the saved session if there is one; when discord no longer considers it
+    /// valid the shard falls back to identifying on its own
+    pub fn configure(&self, shard_id: ShardId, mut builder: ConfigBuilder) -> ConfigBuilder {
+        if let Some(saved) = self.get(shard_id) {
+            builder = builder.session(saved.session.clone());
+
+            if let Some(resume_url) = &saved.resume_url {
+                builder = builder.resume_url(resume_url.clone());
+            }
+        }
+
+        builder
+    }
+}
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
+
+/// coordinates stopping the bot: shard loops stop receiving once it is triggered, and the tasks
+/// spawned through it (interactions being responded to) get to finish before the process exits
+#[derive(Debug, Clone, Default)]
+pub struct Shutdown {
+    token: CancellationToken,
+    tasks: TaskTracker,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn trigger(&self) {
+        self.token.cancel();
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// resolves once shutdown has been triggered
+    pub async fn triggered(&self) {
+        self.token.cancelled().await
+    }
+
+    /// spawns a task that shutdown will wait on
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.tasks.spawn(future);
+    }
+
+    /// waits for every tracked task to finish; `false` if some were still running at the deadline
+    pub async fn wait_for_tasks(&self, deadline: Duration) -> bool {
+        self.tasks.close();
+
+        tokio::time::timeout(deadline, self.tasks.wait())
+            .await
+            .is_ok()
+    }
+
+    pub fn running_tasks(&self) -> usize {
+        self.tasks.len()
+    }
+}
+
+/// resolves on the first SIGINT (ctrl+c) or SIGTERM
+pub async fn signal() -> std::io::Result<&'static str> {
+    #[cfg(unix)]
+    {
+        let mut terminate =
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
+
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result.map(|_| "SIGINT"),
+            _ = terminate.recv() => Ok("SIGTERM"),
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        tokio::signal::ctrl_c().await.map(|_| "SIGINT")
+    }
+}
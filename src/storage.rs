@@ -0,0 +1,15 @@
+use std::path::Path;
+
+use crate::error::BotResult;
+
+/// replaces the file with the bytes; written next to it and renamed, so that a crash mid-write
+/// can't leave half a file
+pub async fn write_atomically(path: impl AsRef<Path>, bytes: impl AsRef<[u8]>) -> BotResult<()> {
+    let path = path.as_ref();
+    let temporary_path = path.with_extension("tmp");
+
+    tokio::fs::write(&temporary_path, bytes).await?;
+    tokio::fs::rename(&temporary_path, path).await?;
+
+    Ok(())
+}
@@ -0,0 +1,735 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use twilight_model::application::command::{
+    Command, CommandOption, CommandOptionChoice, CommandOptionChoiceValue, CommandOptionType,
+    CommandOptionValue, CommandType,
+};
+
+use crate::localization::{DISCORD_LOCALES, choice_value_key};
+
+const MAX_NAME_LENGTH: usize = 32;
+const MAX_DESCRIPTION_LENGTH: usize = 100;
+const MAX_OPTIONS: usize = 25;
+const MAX_CHOICES: usize = 25;
+const MAX_CHOICE_NAME_LENGTH: usize = 100;
+const MAX_CHOICE_VALUE_LENGTH: usize = 100;
+const MAX_STRING_LENGTH: u16 = 6000;
+/// names, descriptions and choice values of a command and everything in it, per locale
+const MAX_COMMAND_CHARACTERS: usize = 8000;
+const MAX_CHAT_INPUT_COMMANDS: usize = 100;
+const MAX_CONTEXT_MENU_COMMANDS: usize = 15;
+
+/// a schema that discord would reject; `path` is shaped like the localization keys of the schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    pub path: String,
+    pub problem: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.problem)
+    }
+}
+
+/// checks the (localized) schemas that are registered together against discord's constraints,
+/// returning every problem instead of stopping at the first
+pub fn validate_command_schemas(schemas: &[Command]) -> Vec<SchemaError> {
+    let mut validator = Validator::default();
+
+    validator.commands(schemas);
+
+    validator.errors
+}
+
+#[derive(Default)]
+struct Validator {
+    errors: Vec<SchemaError>,
+}
+
+impl Validator {
+    fn error(&mut self, path: &str, problem: impl Into<String>) {
+        self.errors.push(SchemaError {
+            path: path.to_owned(),
+            problem: problem.into(),
+        });
+    }
+
+    fn commands(&mut self, schemas: &[Command]) {
+        let mut names = HashSet::new();
+        let mut counts = HashMap::new();
+
+        for command in schemas {
+            let path = format!("commands.{}", command.name);
+
+            if !names.insert((u8::from(command.kind), command.name.as_str())) {
+                self.error(&path, "is registered more than once");
+            }
+
+            *counts.entry(u8::from(command.kind)).or_insert(0) += 1;
+
+            self.command(&path, command);
+        }
+
+        for (kind, limit) in [
+            (CommandType::ChatInput, MAX_CHAT_INPUT_COMMANDS),
+            (CommandType::User, MAX_CONTEXT_MENU_COMMANDS),
+            (CommandType::Message, MAX_CONTEXT_MENU_COMMANDS),
+        ] {
+            let count = counts.get(&u8::from(kind)).copied().unwrap_or(0);
+
+            if count > limit {
+                self.error(
+                    "commands",
+                    format!("has {count} {kind:?} commands, more than the {limit} allowed"),
+                );
+            }
+        }
+    }
+
+    fn command(&mut self, path: &str, command: &Command) {
+        let is_chat_input = command.kind == CommandType::ChatInput;
+
+        self.name(&format!("{path}.name"), &command.name, is_chat_input);
+        self.localized_names(path, command.name_localizations.as_ref(), is_chat_input);
+
+        if is_chat_input {
+            self.description(&format!("{path}.description"), &command.description);
+            self.localized_descriptions(path, command.description_localizations.as_ref());
+            self.options(path, &command.options, Nesting::Command);
+        } else {
+            if !command.description.is_empty() {
+                self.error(
+                    &format!("{path}.description"),
+                    "has to be empty for context menu commands",
+                );
+            }
+
+            if !command.options.is_empty() {
+                self.error(
+                    &format!("{path}.options"),
+                    "context menu commands can't have options",
+                );
+            }
+        }
+
+        for locale in locales_of(
+            command.name_localizations.as_ref(),
+            command.description_localizations.as_ref(),
+        ) {
+            let characters = command_characters(command, locale);
+
+            if characters > MAX_COMMAND_CHARACTERS {
+                self.error(
+                    path,
+                    format!(
+                        "has {characters} characters in {}, more than the {MAX_COMMAND_CHARACTERS} allowed",
+                        locale.unwrap_or("the default locale")
+                    ),
+                );
+            }
+        }
+    }
+
+    fn options(&mut self, path: &str, options: &[CommandOption], nesting: Nesting) {
+        if options.len() > MAX_OPTIONS {
+            self.error(
+                &format!("{path}.options"),
+                format!(
+                    "has {} options, more than the {MAX_OPTIONS} allowed",
+                    options.len()
+                ),
+            );
+        }
+
+        let mut names = HashSet::new();
+        let mut seen_optional = false;
+
+        for option in options {
+            let option_path = format!("{path}.options.{}", option.name);
+
+            if !names.insert(option.name.as_str()) {
+                self.error(&option_path, "has the same name as another option");
+            }
+
+            let is_subcommand = is_subcommand_kind(option.kind);
+
+            match (nesting, option.kind) {
+                (Nesting::Command, _) => (),
+                (Nesting::SubCommandGroup, CommandOptionType::SubCommand) => (),
+                (Nesting::SubCommandGroup, _) => self.error(
+                    &option_path,
+                    "subcommand groups can only contain subcommands",
+                ),
+                (Nesting::SubCommand, _) if is_subcommand => {
+                    self.error(&option_path, "subcommands can't contain subcommands")
+                }
+                (Nesting::SubCommand, _) => (),
+            }
+
+            if is_subcommand
+                && options
+                    .iter()
+                    .any(|option| !is_subcommand_kind(option.kind))
+            {
+                self.error(
+                    &option_path,
+                    "subcommands can't be mixed with other options on the same level",
+                );
+            }
+
+            if option.required.unwrap_or(false) {
+                if seen_optional {
+                    self.error(
+                        &option_path,
+                        "is required but comes after an optional option",
+                    );
+                }
+            } else if !is_subcommand {
+                seen_optional = true;
+            }
+
+            self.option(&option_path, option);
+        }
+    }
+
+    fn option(&mut self, path: &str, option: &CommandOption) {
+        self.name(&format!("{path}.name"), &option.name, true);
+        self.localized_names(path, option.name_localizations.as_ref(), true);
+        self.description(&format!("{path}.description"), &option.description);
+        self.localized_descriptions(path, option.description_localizations.as_ref());
+
+        match option.kind {
+            CommandOptionType::SubCommand => self.options(
+                path,
+                option.options.as_deref().unwrap_or_default(),
+                Nesting::SubCommand,
+            ),
+            CommandOptionType::SubCommandGroup => self.options(
+                path,
+                option.options.as_deref().unwrap_or_default(),
+                Nesting::SubCommandGroup,
+            ),
+            _ => (),
+        }
+
+        if let Some(choices) = &option.choices {
+            self.choices(path, option, choices);
+        }
+
+        if let (Some(min_value), Some(max_value)) = (option.min_value, option.max_value)
+            && value_of(min_value) > value_of(max_value)
+        {
+            self.error(path, "min_value is greater than max_value");
+        }
+
+        if let Some(min_length) = option.min_length
+            && min_length > MAX_STRING_LENGTH
+        {
+            self.error(path, format!("min_length is more than {MAX_STRING_LENGTH}"));
+        }
+
+        if let Some(max_length) = option.max_length
+            && !(1..=MAX_STRING_LENGTH).contains(&max_length)
+        {
+            self.error(
+                path,
+                format!("max_length has to be between 1 and {MAX_STRING_LENGTH}"),
+            );
+        }
+
+        if let (Some(min_length), Some(max_length)) = (option.min_length, option.max_length)
+            && min_length > max_length
+        {
+            self.error(path, "min_length is greater than max_length");
+        }
+    }
+
+    fn choices(&mut self, path: &str, option: &CommandOption, choices: &[CommandOptionChoice]) {
+        if choices.len() > MAX_CHOICES {
+            self.error(
+                &format!("{path}.choices"),
+                format!(
+                    "has {} choices, more than the {MAX_CHOICES} allowed",
+                    choices.len()
+                ),
+            );
+        }
+
+        if option.autocomplete.unwrap_or(false) && !choices.is_empty() {
+            self.error(path, "can't have both choices and autocomplete");
+        }
+
+        let mut values = HashSet::new();
+
+        for choice in choices {
+            let value = choice_value_key(&choice.value);
+            let choice_path = format!("{path}.choices.{value}");
+
+            if !values.insert(value.clone()) {
+                self.error(&choice_path, "has the same value as another choice");
+            }
+
+            if value.chars().count() > MAX_CHOICE_VALUE_LENGTH {
+                self.error(
+                    &choice_path,
+                    format!("value is longer than {MAX_CHOICE_VALUE_LENGTH} characters"),
+                );
+            }
+
+            let valid_kind = matches!(
+                (&choice.value, option.kind),
+                (
+                    CommandOptionChoiceValue::String(_),
+                    CommandOptionType::String
+                ) | (
+                    CommandOptionChoiceValue::Integer(_),
+                    CommandOptionType::Integer
+                ) | (
+                    CommandOptionChoiceValue::Number(_),
+                    CommandOptionType::Number
+                )
+            );
+
+            if !valid_kind {
+                self.error(&choice_path, "value doesn't match the type of the option");
+            }
+
+            self.choice_name(&format!("{choice_path}.name"), &choice.name);
+
+            for (locale, name) in sorted(choice.name_localizations.as_ref()) {
+                let name_path = format!("{choice_path}.name ({locale})");
+
+                self.locale(&name_path, locale);
+                self.choice_name(&name_path, name);
+            }
+        }
+    }
+
+    fn name(&mut self, path: &str, name: &str, is_chat_input: bool) {
+        let length = name.chars().count();
+
+        if !(1..=MAX_NAME_LENGTH).contains(&length) {
+            self.error(
+                path,
+                format!("\"{name}\" has to be between 1 and {MAX_NAME_LENGTH} characters"),
+            );
+        }
+
+        if !is_chat_input {
+            return;
+        }
+
+        if let Some(character) = name
+            .chars()
+            .find(|character| !is_name_character(*character))
+        {
+            self.error(
+                path,
+                format!("\"{name}\" contains \"{character}\", which isn't allowed in names"),
+            );
+        }
+
+        if name.chars().any(char::is_uppercase) {
+            self.error(path, format!("\"{name}\" has to be lowercase"));
+        }
+    }
+
+    fn description(&mut self, path: &str, description: &str) {
+        let length = description.chars().count();
+
+        if !(1..=MAX_DESCRIPTION_LENGTH).contains(&length) {
+            self.error(
+                path,
+                format!("has to be between 1 and {MAX_DESCRIPTION_LENGTH} characters"),
+            );
+        }
+    }
+
+    fn choice_name(&mut self, path: &str, name: &str) {
+        let length = name.chars().count();
+
+        if !(1..=MAX_CHOICE_NAME_LENGTH).contains(&length) {
+            self.error(
+                path,
+                format!("has to be between 1 and {MAX_CHOICE_NAME_LENGTH} characters"),
+            );
+        }
+    }
+
+    fn localized_names(
+        &mut self,
+        path: &str,
+        localizations: Option<&HashMap<String, String>>,
+        is_chat_input: bool,
+    ) {
+        for (locale, name) in sorted(localizations) {
+            let name_path = format!("{path}.name ({locale})");
+
+            self.locale(&name_path, locale);
+            self.name(&name_path, name, is_chat_input);
+        }
+    }
+
+    fn localized_descriptions(
+        &mut self,
+        path: &str,
+        localizations: Option<&HashMap<String, String>>,
+    ) {
+        for (locale, description) in sorted(localizations) {
+            let description_path = format!("{path}.description ({locale})");
+
+            self.locale(&description_path, locale);
+            self.description(&description_path, description);
+        }
+    }
+
+    fn locale(&mut self, path: &str, locale: &str) {
+        if !DISCORD_LOCALES.contains(&locale) {
+            self.error(
+                path,
+                format!("\"{locale}\" is not a locale discord supports"),
+            );
+        }
+    }
+}
+
+/// which options a level of options is allowed to contain
+#[derive(Debug, Clone, Copy)]
+enum Nesting {
+    Command,
+    SubCommandGroup,
+    SubCommand,
+}
+
+fn is_subcommand_kind(kind: CommandOptionType) -> bool {
+    matches!(
+        kind,
+        CommandOptionType::SubCommand | CommandOptionType::SubCommandGroup
+    )
+}
+
+/// letters and numbers of any script (devanagari and thai marks included), `-`, `_` and `'`
+fn is_name_character(character: char) -> bool {
+    character.is_alphanumeric()
+        || matches!(character, '-' | '_' | '\'')
+        || ('\u{0900}'..='\u{097F}').contains(&character)
+        || ('\u{0E00}'..='\u{0E7F}').contains(&character)
+}
+
+fn value_of(value: CommandOptionValue) -> f64 {
+    match value {
+        CommandOptionValue::Integer(value) => value as f64,
+        CommandOptionValue::Number(value) => value,
+    }
+}
+
+/// so that errors come out in the same order every time
+fn sorted(localizations: Option<&HashMap<String, String>>) -> Vec<(&str, &str)> {
+    let mut localizations: Vec<_> = localizations
+        .into_iter()
+        .flatten()
+        .map(|(locale, value)| (locale.as_str(), value.as_str()))
+        .collect();
+
+    localizations.sort_unstable();
+    localizations
+}
+
+/// `None` stands for the default locale
+fn locales_of<'a>(
+    name_localizations: Option<&'a HashMap<String, String>>,
+    description_localizations: Option<&'a HashMap<String, String>>,
+) -> Vec<Option<&'a str>> {
+    let mut locales: Vec<_> = name_localizations
+        .into_iter()
+        .chain(description_localizations)
+        .flat_map(HashMap::keys)
+        .map(|locale| Some(locale.as_str()))
+        .collect();
+
+    locales.sort_unstable();
+    locales.dedup();
+    locales.insert(0, None);
+    locales
+}
+
+fn localized<'a>(
+    value: &'a str,
+    localizations: Option<&'a HashMap<String, String>>,
+    locale: Option<&str>,
+) -> &'a str {
+    locale
+        .and_then(|locale| localizations?.get(locale))
+        .map_or(value, String::as_str)
+}
+
+fn command_characters(command: &Command, locale: Option<&str>) -> usize {
+    localized(&command.name, command.name_localizations.as_ref(), locale)
+        .chars()
+        .count()
+        + localized(
+            &command.description,
+            command.description_localizations.as_ref(),
+            locale,
+        )
+        .chars()
+        .count()
+        + command
+            .options
+            .iter()
+            .map(|option| option_characters(option, locale))
+            .sum::<usize>()
+}
+
+fn option_characters(option: &CommandOption, locale: Option<&str>) -> usize {
+    let choices = option.choices.iter().flatten().map(|choice| {
+        localized(&choice.name, choice.name_localizations.as_ref(), locale)
+            .chars()
+            .count()
+            + choice_value_key(&choice.value).chars().count()
+    });
+
+    localized(&option.name, option.name_localizations.as_ref(), locale)
+        .chars()
+        .count()
+        + localized(
+            &option.description,
+            option.description_localizations.as_ref(),
+            locale,
+        )
+        .chars()
+        .count()
+        + choices.sum::<usize>()
+        + option
+            .options
+            .iter()
+            .flatten()
+            .map(|option| option_characters(option, locale))
+            .sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_util::builder::command::{
+        CommandBuilder, IntegerBuilder, StringBuilder, SubCommandBuilder, SubCommandGroupBuilder,
+    };
+
+    use super::*;
+    use crate::{commands, config::Features, registration::CommandSchemas};
+
+    fn chat_input(name: &str) -> CommandBuilder {
+        CommandBuilder::new(name, "a command", CommandType::ChatInput)
+    }
+
+    fn string(name: &str) -> StringBuilder {
+        StringBuilder::new(name, "an option")
+    }
+
+    /// the paths of the errors of a single command
+    fn error_paths(command: Command) -> Vec<String> {
+        validate_command_schemas(&[command])
+            .into_iter()
+            .map(|error| error.path)
+            .collect()
+    }
+
+    #[test]
+    fn bundled_commands_are_valid() {
+        let features = Features {
+            debug_commands: true,
+            guild_commands: false,
+            clear_guild_commands: false,
+        };
+        let schemas = CommandSchemas::new(&commands::command_registry(&features), &features);
+
+        assert_eq!(schemas.validate(), []);
+    }
+
+    #[test]
+    fn checks_names() {
+        assert!(error_paths(chat_input("ping-2_x'").build()).is_empty());
+        assert!(error_paths(chat_input("पिंग").build()).is_empty());
+
+        for name in [
+            "",
+            "Ping",
+            "pi ng",
+            "pi.ng",
+            &"a".repeat(MAX_NAME_LENGTH + 1),
+        ] {
+            assert_eq!(
+                error_paths(chat_input(name).build()),
+                [format!("commands.{name}.name")],
+                "{name:?}"
+            );
+        }
+
+        // context menu commands can have spaces and uppercase letters, but not be too long
+        let context_menu = |name: &str| CommandBuilder::new(name, "", CommandType::Message).build();
+
+        assert!(error_paths(context_menu("Echo Message")).is_empty());
+        assert_eq!(
+            error_paths(context_menu(&"a".repeat(MAX_NAME_LENGTH + 1))).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn checks_descriptions() {
+        let command = |description: &str| {
+            CommandBuilder::new("ping", description, CommandType::ChatInput).build()
+        };
+
+        assert!(error_paths(command(&"a".repeat(MAX_DESCRIPTION_LENGTH))).is_empty());
+        assert_eq!(error_paths(command("")), ["commands.ping.description"]);
+        assert_eq!(
+            error_paths(command(&"a".repeat(MAX_DESCRIPTION_LENGTH + 1))),
+            ["commands.ping.description"]
+        );
+        assert_eq!(
+            error_paths(CommandBuilder::new("Echo", "echoes", CommandType::Message).build()),
+            ["commands.Echo.description"]
+        );
+    }
+
+    #[test]
+    fn checks_the_option_count() {
+        let command = |count: usize| {
+            (0..count)
+                .fold(chat_input("many"), |command, index| {
+                    command.option(string(&format!("option{index}")))
+                })
+                .build()
+        };
+
+        assert!(error_paths(command(MAX_OPTIONS)).is_empty());
+        assert_eq!(
+            error_paths(command(MAX_OPTIONS + 1)),
+            ["commands.many.options"]
+        );
+    }
+
+    #[test]
+    fn requires_required_options_first() {
+        let command = chat_input("roll")
+            .option(string("first").required(true))
+            .option(string("second"))
+            .option(string("third").required(true))
+            .build();
+
+        assert_eq!(error_paths(command), ["commands.roll.options.third"]);
+    }
+
+    #[test]
+    fn checks_choices() {
+        let with_choices =
+            |count: usize| {
+                chat_input("pick")
+                    .option(string("choice").choices(
+                        (0..count).map(|index| (format!("c{index}"), format!("c{index}"))),
+                    ))
+                    .build()
+            };
+
+        assert!(error_paths(with_choices(MAX_CHOICES)).is_empty());
+        assert_eq!(
+            error_paths(with_choices(MAX_CHOICES + 1)),
+            ["commands.pick.options.choice.choices"]
+        );
+
+        let duplicated = chat_input("pick")
+            .option(string("choice").choices([("a", "same"), ("b", "same")]))
+            .build();
+
+        assert_eq!(
+            error_paths(duplicated),
+            ["commands.pick.options.choice.choices.same"]
+        );
+
+        let mut mistyped = chat_input("pick")
+            .option(IntegerBuilder::new("number", "a number").choices([("one", 1)]))
+            .build();
+        mistyped.options[0].choices.as_mut().unwrap()[0].value =
+            CommandOptionChoiceValue::String(String::from("1"));
+
+        assert_eq!(
+            error_paths(mistyped),
+            ["commands.pick.options.number.choices.1"]
+        );
+
+        let mut autocompleted = with_choices(1);
+        autocompleted.options[0].autocomplete = Some(true);
+
+        assert_eq!(error_paths(autocompleted), ["commands.pick.options.choice"]);
+    }
+
+    #[test]
+    fn checks_localization_locales() {
+        let command = chat_input("ping")
+            .name_localizations([("ja", "ping"), ("xx", "ping")])
+            .description_localizations([("Japanese", "ポン")])
+            .build();
+
+        assert_eq!(
+            error_paths(command),
+            [
+                "commands.ping.name (xx)",
+                "commands.ping.description (Japanese)"
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_subcommand_nesting() {
+        let mut nested = chat_input("deep")
+            .option(SubCommandBuilder::new("outer", "a subcommand"))
+            .build();
+        nested.options[0].options = Some(vec![
+            SubCommandBuilder::new("inner", "a subcommand").build(),
+        ]);
+
+        assert_eq!(
+            error_paths(nested),
+            ["commands.deep.options.outer.options.inner"]
+        );
+
+        let group = chat_input("grouped")
+            .option(
+                SubCommandGroupBuilder::new("group", "a group")
+                    .subcommands([SubCommandBuilder::new("sub", "a subcommand")]),
+            )
+            .build();
+
+        assert!(error_paths(group).is_empty());
+
+        let mut group_with_option = chat_input("grouped")
+            .option(SubCommandGroupBuilder::new("group", "a group"))
+            .build();
+        group_with_option.options[0].options = Some(vec![string("text").build()]);
+
+        assert_eq!(
+            error_paths(group_with_option),
+            ["commands.grouped.options.group.options.text"]
+        );
+
+        let mixed = chat_input("mixed")
+            .option(SubCommandBuilder::new("sub", "a subcommand"))
+            .option(string("text"))
+            .build();
+
+        assert_eq!(error_paths(mixed), ["commands.mixed.options.sub"]);
+    }
+
+    #[test]
+    fn rejects_duplicate_commands() {
+        let errors =
+            validate_command_schemas(&[chat_input("ping").build(), chat_input("ping").build()]);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "commands.ping");
+    }
+}